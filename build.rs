@@ -1,20 +1,27 @@
 // This file is licensed under the same terms as ICU4X.
 // For details, please see the LICENSE file.
 
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::fmt::Write as _;
 use std::path::PathBuf;
 
 use icu_provider::export::DataExporter;
 use icu_provider::ResourceKey;
 use icu_provider_blob::export::BlobExporter;
 use icu_provider_uprops::PropertiesDataProvider;
+use serde::Deserialize;
 
 fn raw_data_dir() -> PathBuf {
     PathBuf::from(std::env!("CARGO_MANIFEST_DIR")).join("data")
 }
 
+fn out_dir() -> PathBuf {
+    PathBuf::from(std::env::var("OUT_DIR").unwrap())
+}
+
 fn output_path() -> PathBuf {
-    PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("uprops.bincode")
+    out_dir().join("uprops.bincode")
 }
 
 fn get_all_uprops_keys() -> Vec<ResourceKey> {
@@ -33,6 +40,679 @@ fn get_all_uprops_keys() -> Vec<ResourceKey> {
     keys
 }
 
+#[derive(Deserialize)]
+struct EnumeratedPropertyToml {
+    enum_property: Vec<EnumeratedPropertyData>,
+}
+
+#[derive(Deserialize)]
+struct EnumeratedPropertyData {
+    ranges: Vec<EnumeratedPropertyRange>,
+}
+
+#[derive(Deserialize)]
+struct EnumeratedPropertyRange {
+    a: u32,
+    b: u32,
+    v: u16,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ScriptExtensionsToml {
+    script_extensions: Vec<ScriptExtensionsData>,
+}
+
+#[derive(Deserialize)]
+struct ScriptExtensionsData {
+    code_point_count: u32,
+    ranges: Vec<ScriptExtensionsRange>,
+}
+
+#[derive(Deserialize)]
+struct ScriptExtensionsRange {
+    a: u32,
+    b: u32,
+    v: String,
+}
+
+/// The names of a property, as found in every data file.
+#[derive(Deserialize)]
+struct PropertyNames {
+    long_name: String,
+    short_name: String,
+}
+
+#[derive(Deserialize)]
+struct PropertyNamesToml {
+    #[serde(default)]
+    binary_property: Vec<PropertyNames>,
+    #[serde(default)]
+    enum_property: Vec<PropertyNames>,
+    #[serde(default)]
+    script_extensions: Vec<PropertyNames>,
+}
+
+fn read_toml<T: serde::de::DeserializeOwned>(name: &str) -> Result<T, Box<dyn Error>> {
+    let mut path = raw_data_dir().join(name);
+    path.set_extension("toml");
+    Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+/// Maps the short name of each Script value to its numeric value.
+fn get_script_values() -> Result<HashMap<String, u16>, Box<dyn Error>> {
+    let sc: EnumeratedPropertyToml = read_toml("sc")?;
+    let mut values = HashMap::new();
+    for range in sc.enum_property.into_iter().flat_map(|p| p.ranges) {
+        values.insert(range.name, range.v);
+    }
+    Ok(values)
+}
+
+/// Reads the `;`-separated fields of each line of a UCD data file,
+/// skipping comments.
+fn read_ucd_fields(name: &str) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(raw_data_dir().join(name))?;
+    Ok(text
+        .lines()
+        .map(|line| line.split('#').next().unwrap())
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split(';')
+                .map(|field| field.trim().to_string())
+                .collect()
+        })
+        .collect())
+}
+
+/// Returns the names of the property and its aliases from the fields of
+/// a UCD alias file, long name first.
+fn get_names(fields: &[String]) -> Vec<&str> {
+    let (short, long, others) = (&fields[0], &fields[1], &fields[2..]);
+    let mut names = vec![long.as_str()];
+    if short != long {
+        names.push(short);
+    }
+    names.extend(others.iter().map(|name| name.as_str()));
+    names
+}
+
+/// Converts a property name like `Changes_When_NFKC_Casefolded` to the
+/// name of the corresponding variant, like `ChangesWhenNfkcCasefolded`.
+fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the name of the `GeneralCategory` variant for the long name
+/// of a General_Category value.
+fn general_category_variant(long_name: &str) -> String {
+    // ICU4X calls Decimal_Number `Digit`.
+    match long_name {
+        "Decimal_Number" => "Digit".to_string(),
+        name => to_camel_case(name),
+    }
+}
+
+/// Generates the alias tables of Tables 69-72 of ECMA-262. The names
+/// come from data/PropertyAliases.txt and data/PropertyValueAliases.txt,
+/// and the data keys come from the .toml files.
+///
+/// Every property and value in the tables must have data in the blob.
+/// Otherwise, using that property would panic at runtime, so we fail
+/// the build instead.
+fn write_tables(
+    keys: &[ResourceKey],
+    script_values: &HashMap<String, u16>,
+) -> Result<(), Box<dyn Error>> {
+    let exported: HashSet<String> = keys
+        .iter()
+        .map(|key| key.sub_category.to_string())
+        .collect();
+    let mut missing = vec![];
+
+    // Maps the long name of each property with a data file to whether it
+    // is binary and its short name.
+    let mut properties = HashMap::new();
+    for entry in std::fs::read_dir(raw_data_dir())? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+            continue;
+        }
+        let toml: PropertyNamesToml = toml::from_str(&std::fs::read_to_string(path)?)?;
+        for names in toml.binary_property {
+            properties.insert(names.long_name, (true, names.short_name));
+        }
+        for names in toml.enum_property.into_iter().chain(toml.script_extensions) {
+            properties.insert(names.long_name, (false, names.short_name));
+        }
+    }
+
+    let mut enumerated = vec![];
+    let mut binary = vec![];
+    for fields in read_ucd_fields("PropertyAliases.txt")? {
+        let names = get_names(&fields);
+        let variant = to_camel_case(names[0]);
+        match properties.get(names[0]) {
+            Some((true, key)) => {
+                if !exported.contains(key) {
+                    missing.push(key.clone());
+                }
+                let row = format!("({:?}, &{:?}, BinaryProperty::{})", key, names, variant);
+                binary.push((names[0].to_string(), row));
+            }
+            Some((false, _)) => {
+                let row = format!("(&{:?}, EnumeratedProperty::{})", names, variant);
+                enumerated.push((names[0].to_string(), row));
+            }
+            None => missing.push(names[0].to_string()),
+        }
+    }
+
+    let mut general_categories = vec![];
+    let mut scripts = vec![];
+    for fields in read_ucd_fields("PropertyValueAliases.txt")? {
+        let (prop, fields) = (&fields[0], &fields[1..]);
+        let names = get_names(fields);
+        let key = format!("{}={}", prop, fields[0]);
+        match prop.as_str() {
+            "gc" => {
+                let variant = general_category_variant(names[0]);
+                if !exported.contains(&key) {
+                    missing.push(key.clone());
+                }
+                let row = format!("({:?}, &{:?}, GeneralCategory::{})", key, names, variant);
+                general_categories.push((names[0].to_string(), row));
+            }
+            "sc" => {
+                // Scripts without any code points, like Katakana_Or_Hiragana,
+                // have no data and are not supported.
+                if let Some(value) = script_values.get(&fields[0]) {
+                    if !exported.contains(&key) {
+                        missing.push(key.clone());
+                    }
+                    let row = format!("({:?}, &{:?}, Script({}))", key, names, value);
+                    scripts.push((names[0].to_string(), row));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(format!("Missing static data for: {}", missing.join(", ")).into());
+    }
+
+    let mut out = String::new();
+    write_table(
+        &mut out,
+        "ENUMERATED_PROPERTIES: &[(&[&str], EnumeratedProperty)]",
+        enumerated,
+    )?;
+    write_table(
+        &mut out,
+        "BINARY_PROPERTIES: &[(&str, &[&str], BinaryProperty)]",
+        binary,
+    )?;
+    write_table(
+        &mut out,
+        "GENERAL_CATEGORIES: &[(&str, &[&str], GeneralCategory)]",
+        general_categories,
+    )?;
+    write_table(&mut out, "SCRIPTS: &[(&str, &[&str], Script)]", scripts)?;
+
+    std::fs::write(out_dir().join("tables.rs"), out)?;
+    Ok(())
+}
+
+/// Writes a static table with the given name and type, with one row for
+/// each (long name, row) pair, sorted by long name.
+fn write_table(
+    out: &mut String,
+    declaration: &str,
+    mut rows: Vec<(String, String)>,
+) -> Result<(), Box<dyn Error>> {
+    rows.sort();
+    writeln!(out, "pub(crate) static {} = &[", declaration)?;
+    for (_, row) in rows {
+        writeln!(out, "    {},", row)?;
+    }
+    writeln!(out, "];")?;
+    Ok(())
+}
+
+/// Script_Extensions is not supported by `PropertiesDataProvider`, so
+/// we export it as a table of the code points whose Script_Extensions
+/// value differs from their Script value. The table is sorted, and the
+/// ranges must not overlap, so that it can be binary searched. They must
+/// also cover as many code points as ScriptExtensions.txt lists, so that
+/// a partial table fails the build.
+fn write_script_extensions(script_values: &HashMap<String, u16>) -> Result<(), Box<dyn Error>> {
+    let scx: ScriptExtensionsToml = read_toml("scx")?;
+    for data in &scx.script_extensions {
+        let count: u32 = data.ranges.iter().map(|range| range.b - range.a + 1).sum();
+        if count != data.code_point_count {
+            return Err(format!(
+                "scx.toml lists {} code points, but its source lists {}",
+                count, data.code_point_count
+            )
+            .into());
+        }
+    }
+    let mut ranges: Vec<&ScriptExtensionsRange> = scx
+        .script_extensions
+        .iter()
+        .flat_map(|p| &p.ranges)
+        .collect();
+    ranges.sort_by_key(|range| range.a);
+    for pair in ranges.windows(2) {
+        if pair[0].b >= pair[1].a {
+            return Err(format!(
+                "Overlapping ranges in scx.toml: {:x}..{:x} and {:x}..{:x}",
+                pair[0].a, pair[0].b, pair[1].a, pair[1].b
+            )
+            .into());
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "pub(crate) static SCRIPT_EXTENSIONS: &[(char, char, &[Script])] = &["
+    )?;
+    for range in ranges {
+        let mut scripts = vec![];
+        for name in range.v.split_whitespace() {
+            let value = script_values
+                .get(name)
+                .ok_or_else(|| format!("Unknown script in scx.toml: {}", name))?;
+            scripts.push(format!("Script({})", value));
+        }
+        writeln!(
+            out,
+            "    ('\\u{{{:x}}}', '\\u{{{:x}}}', &[{}]),",
+            range.a,
+            range.b,
+            scripts.join(", ")
+        )?;
+    }
+    writeln!(out, "];")?;
+
+    std::fs::write(out_dir().join("scx.rs"), out)?;
+    Ok(())
+}
+
+/// The properties of strings are not supported by
+/// `PropertiesDataProvider` either, so we export the emoji sequences of
+/// data/emoji-sequences.txt and data/emoji-zwj-sequences.txt as tables of
+/// code point ranges and strings.
+fn write_emoji_sequences() -> Result<(), Box<dyn Error>> {
+    let mut code_points = String::new();
+    let mut strings = String::new();
+    for file in &["emoji-sequences.txt", "emoji-zwj-sequences.txt"] {
+        for fields in read_ucd_fields(file)? {
+            // A type field without a matching variant fails to compile.
+            let variant = to_camel_case(&fields[1]);
+            if fields[0].contains(' ') {
+                let string: String = fields[0]
+                    .split_whitespace()
+                    .map(|code_point| format!("\\u{{{}}}", code_point))
+                    .collect();
+                writeln!(
+                    strings,
+                    "    (StringProperty::{}, \"{}\"),",
+                    variant, string
+                )?;
+            } else {
+                let range = fields[0].as_str();
+                let (start, end) = range.split_once("..").unwrap_or((range, range));
+                writeln!(
+                    code_points,
+                    "    (StringProperty::{}, '\\u{{{}}}', '\\u{{{}}}'),",
+                    variant, start, end
+                )?;
+            }
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "pub(crate) static EMOJI_CODE_POINTS: &[(StringProperty, char, char)] = &[\n{}];",
+        code_points
+    )?;
+    writeln!(
+        out,
+        "pub(crate) static EMOJI_SEQUENCES: &[(StringProperty, &str)] = &[\n{}];",
+        strings
+    )?;
+
+    std::fs::write(out_dir().join("emoji.rs"), out)?;
+    Ok(())
+}
+
+/// Code points are looked up in three steps: bits 10-20 select a block
+/// of `TRIE_INDEX2_BLOCK` entries of the second index, bits 6-9 select
+/// an entry of that block, and the entry selects a block of
+/// `TRIE_LEAF_BLOCK` values, of which bits 0-5 select the value.
+/// Identical blocks are stored once. Both sizes are written to
+/// code_point_tries.rs, and the lookup derives its shifts from them, so
+/// they must be powers of two.
+const TRIE_INDEX2_BLOCK: usize = 16;
+const TRIE_LEAF_BLOCK: usize = 64;
+
+struct CodePointTrie {
+    index1: Vec<u16>,
+    index2: Vec<u16>,
+    leaves: Vec<u16>,
+}
+
+fn build_trie(values: &[u16]) -> CodePointTrie {
+    fn dedup_blocks(values: &[u16], block_size: usize, blocks: &mut Vec<u16>) -> Vec<u16> {
+        let mut seen: HashMap<&[u16], u16> = HashMap::new();
+        let mut index = vec![];
+        for block in values.chunks(block_size) {
+            let next = seen.len() as u16;
+            let number = *seen.entry(block).or_insert_with(|| {
+                blocks.extend_from_slice(block);
+                next
+            });
+            index.push(number);
+        }
+        index
+    }
+
+    let mut leaves = vec![];
+    let leaf_index = dedup_blocks(values, TRIE_LEAF_BLOCK, &mut leaves);
+    let mut index2 = vec![];
+    let index1 = dedup_blocks(&leaf_index, TRIE_INDEX2_BLOCK, &mut index2);
+    CodePointTrie {
+        index1,
+        index2,
+        leaves,
+    }
+}
+
+/// The value of every code point of an enumerated property, and the
+/// short name of each value.
+struct CodePointValues {
+    values: Vec<u16>,
+    names: HashMap<u16, String>,
+}
+
+/// Reads the values of an enumerated property from its .toml file.
+fn read_code_point_values(name: &str) -> Result<CodePointValues, Box<dyn Error>> {
+    let toml: EnumeratedPropertyToml = read_toml(name)?;
+    let mut values = vec![None; 0x110000];
+    let mut names = HashMap::new();
+    for range in toml.enum_property.into_iter().flat_map(|p| p.ranges) {
+        for value in &mut values[range.a as usize..=range.b as usize] {
+            *value = Some(range.v);
+        }
+        names.insert(range.v, range.name);
+    }
+    let values = values
+        .into_iter()
+        .enumerate()
+        .map(|(c, value)| value.ok_or_else(|| format!("U+{:04X} has no value in {}.toml", c, name)))
+        .collect::<Result<_, _>>()?;
+    Ok(CodePointValues { values, names })
+}
+
+fn write_trie(
+    out: &mut String,
+    declaration: &str,
+    trie: &CodePointTrie,
+    value: impl Fn(u16) -> String,
+    default: &str,
+) -> Result<(), Box<dyn Error>> {
+    writeln!(out, "pub(crate) static {} = CodePointTrie {{", declaration)?;
+    writeln!(out, "    index1: &{:?},", trie.index1)?;
+    writeln!(out, "    index2: &{:?},", trie.index2)?;
+    let leaves: Vec<String> = trie.leaves.iter().map(|&v| value(v)).collect();
+    writeln!(out, "    leaves: &[{}],", leaves.join(", "))?;
+    writeln!(out, "    default: {},", default)?;
+    writeln!(out, "}};")?;
+    Ok(())
+}
+
+/// Writes the General_Category and Script value of every code point as
+/// a `CodePointTrie`.
+fn write_code_point_tries(script_values: &HashMap<String, u16>) -> Result<(), Box<dyn Error>> {
+    // Maps the short name of each General_Category value to its variant.
+    let mut variants = HashMap::new();
+    for fields in read_ucd_fields("PropertyValueAliases.txt")? {
+        if fields[0] == "gc" {
+            variants.insert(fields[1].clone(), general_category_variant(&fields[2]));
+        }
+    }
+
+    if !TRIE_INDEX2_BLOCK.is_power_of_two() || !TRIE_LEAF_BLOCK.is_power_of_two() {
+        return Err("The block sizes of the code point tries must be powers of two".into());
+    }
+    let mut out = String::new();
+    writeln!(
+        out,
+        "pub(crate) const TRIE_INDEX2_BLOCK: usize = {};",
+        TRIE_INDEX2_BLOCK
+    )?;
+    writeln!(
+        out,
+        "pub(crate) const TRIE_LEAF_BLOCK: usize = {};",
+        TRIE_LEAF_BLOCK
+    )?;
+    let gc = read_code_point_values("gc")?;
+    write_trie(
+        &mut out,
+        "GENERAL_CATEGORY_TRIE: CodePointTrie<GeneralCategory>",
+        &build_trie(&gc.values),
+        |v| format!("GeneralCategory::{}", variants[&gc.names[&v]]),
+        "GeneralCategory::Unassigned",
+    )?;
+    let sc = read_code_point_values("sc")?;
+    write_trie(
+        &mut out,
+        "SCRIPT_TRIE: CodePointTrie<Script>",
+        &build_trie(&sc.values),
+        |v| format!("Script({})", v),
+        &format!("Script({})", script_values["Zzzz"]),
+    )?;
+
+    std::fs::write(out_dir().join("code_point_tries.rs"), out)?;
+    Ok(())
+}
+
+/// Writes the simple case folding of data/CaseFolding.txt, which is the
+/// mapping with status C or S, as a table sorted by code point.
+fn write_case_folding() -> Result<(), Box<dyn Error>> {
+    let mut out = String::new();
+    writeln!(out, "pub(crate) static CASE_FOLDING: &[(u32, u32)] = &[")?;
+    for fields in read_ucd_fields("CaseFolding.txt")? {
+        if fields[1] == "C" || fields[1] == "S" {
+            writeln!(out, "    (0x{}, 0x{}),", fields[0], fields[2])?;
+        }
+    }
+    writeln!(out, "];")?;
+
+    std::fs::write(out_dir().join("case_folding.rs"), out)?;
+    Ok(())
+}
+
+/// Writes the full uppercase mapping of data/UppercaseMapping.txt as a
+/// table sorted by code point.
+fn write_uppercase_mapping() -> Result<(), Box<dyn Error>> {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "pub(crate) static UPPERCASE_MAPPING: &[(u32, &[u32])] = &["
+    )?;
+    for fields in read_ucd_fields("UppercaseMapping.txt")? {
+        let mapping: Vec<String> = fields[1]
+            .split_whitespace()
+            .map(|code_point| format!("0x{}", code_point))
+            .collect();
+        writeln!(out, "    (0x{}, &[{}]),", fields[0], mapping.join(", "))?;
+    }
+    writeln!(out, "];")?;
+
+    std::fs::write(out_dir().join("uppercase_mapping.rs"), out)?;
+    Ok(())
+}
+
+/// Returns the sources named in the `# Source:` lines of the header of a
+/// file derived from Unicode data, with their versions: for
+/// `# Source: https://unicode.org/Public/14.0.0/ucd/UnicodeData.txt`,
+/// "UnicodeData.txt" and "14.0.0", and for
+/// `# Source: https://unicode.org/Public/emoji/14.0/emoji-sequences.txt`,
+/// "emoji-sequences.txt" and "14.0".
+fn read_source_versions(name: &str) -> Result<Vec<(String, String)>, Box<dyn Error>> {
+    let contents = std::fs::read_to_string(raw_data_dir().join(name))?;
+    let sources = contents
+        .lines()
+        .take_while(|line| line.starts_with('#'))
+        .filter_map(|line| line.strip_prefix("# Source:"))
+        .map(|url| {
+            let path = url
+                .trim()
+                .strip_prefix("https://unicode.org/Public/")
+                .ok_or_else(|| format!("Unknown source {} in {}", url.trim(), name))?;
+            let mut components = path.split('/');
+            let version = match components.next() {
+                Some("emoji") => components.next(),
+                first => first,
+            }
+            .unwrap_or("");
+            let source = path.rsplit('/').next().unwrap_or("");
+            Ok((source.to_string(), version.to_string()))
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    if sources.is_empty() {
+        return Err(format!("No source in the header of {}", name).into());
+    }
+    Ok(sources)
+}
+
+/// Returns the Unicode version of the data files, from the sources named
+/// in their headers, and fails if they are not all derived from the same
+/// version. Emoji versions have two components, which must match the
+/// first two of the UCD version. The other .toml files, which are exported
+/// from ICU4X, do not record a version.
+fn check_data_versions() -> Result<String, Box<dyn Error>> {
+    let (_, version) = read_source_versions("PropertyAliases.txt")?.remove(0);
+    for name in &[
+        "PropertyAliases.txt",
+        "PropertyValueAliases.txt",
+        "CaseFolding.txt",
+        "UppercaseMapping.txt",
+        "scx.toml",
+    ] {
+        for (source, other) in read_source_versions(name)? {
+            if other != version {
+                return Err(format!(
+                    "PropertyAliases.txt is derived from version {}, but {} is derived from {} version {}",
+                    version, name, source, other
+                )
+                .into());
+            }
+        }
+    }
+    for name in &["emoji-sequences.txt", "emoji-zwj-sequences.txt"] {
+        for (source, emoji_version) in read_source_versions(name)? {
+            if !version.starts_with(&format!("{}.", emoji_version)) {
+                return Err(format!(
+                    "PropertyAliases.txt is derived from version {}, but {} is derived from {} version {}",
+                    version, name, source, emoji_version
+                )
+                .into());
+            }
+        }
+    }
+    Ok(version)
+}
+
+/// An FNV-1a hash of the data, which does not depend on the formatting
+/// of the code generated by build.rs.
+struct DataHasher(u64);
+
+impl DataHasher {
+    fn new() -> Self {
+        DataHasher(0xcbf29ce484222325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    /// Hashes a string and a separator, so that "ab", "c" and "a", "bc"
+    /// hash differently.
+    fn write_str(&mut self, s: &str) {
+        self.write(s.as_bytes());
+        self.write(&[0]);
+    }
+}
+
+/// Writes the Unicode version of the data, the keys exported to the
+/// blob, and a checksum of the data: the blob, the fields of the UCD and
+/// emoji files, and the Script_Extensions ranges. Comments in the data
+/// files do not affect it. This must run after the blob has been written.
+fn write_data_info(keys: &[ResourceKey]) -> Result<(), Box<dyn Error>> {
+    let version = check_data_versions()?;
+
+    let mut hasher = DataHasher::new();
+    hasher.write(&std::fs::read(output_path())?);
+    for name in &[
+        "PropertyAliases.txt",
+        "PropertyValueAliases.txt",
+        "emoji-sequences.txt",
+        "emoji-zwj-sequences.txt",
+        "CaseFolding.txt",
+        "UppercaseMapping.txt",
+    ] {
+        hasher.write_str(name);
+        for fields in read_ucd_fields(name)? {
+            for field in &fields {
+                hasher.write_str(field);
+            }
+            hasher.write(b"\n");
+        }
+    }
+    let scx: ScriptExtensionsToml = read_toml("scx")?;
+    for range in scx.script_extensions.iter().flat_map(|p| &p.ranges) {
+        hasher.write(&range.a.to_le_bytes());
+        hasher.write(&range.b.to_le_bytes());
+        hasher.write_str(&range.v);
+    }
+
+    let mut out = String::new();
+    writeln!(out, "pub(crate) static UCD_VERSION: &str = {:?};", version)?;
+    writeln!(out, "pub(crate) static DATA_KEYS: &[&str] = &[")?;
+    for key in keys {
+        writeln!(out, "    {:?},", &*key.sub_category)?;
+    }
+    writeln!(out, "];")?;
+    writeln!(
+        out,
+        "pub(crate) static DATA_CHECKSUM: u64 = 0x{:016x};",
+        hasher.0
+    )?;
+
+    std::fs::write(out_dir().join("data_info.rs"), out)?;
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     println!("cargo:rerun-if-changed=data");
 
@@ -42,11 +722,21 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut exporter = BlobExporter::new_with_sink(sink);
 
     let keys = get_all_uprops_keys();
+    let script_values = get_script_values()?;
+    write_tables(&keys, &script_values)?;
+
     for key in keys.iter() {
         icu_provider::export::export_from_iterable(key, &fs_provider, &mut exporter)?
     }
 
     exporter.close()?;
 
+    write_script_extensions(&script_values)?;
+    write_emoji_sequences()?;
+    write_code_point_tries(&script_values)?;
+    write_case_folding()?;
+    write_uppercase_mapping()?;
+    write_data_info(&keys)?;
+
     Ok(())
 }
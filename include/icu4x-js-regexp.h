@@ -8,13 +8,131 @@
 extern "C" {
 #endif
 
+#include <stdbool.h>
 #include <stdint.h>
 
 typedef struct ICU4XUniset ICU4XUniset;
+typedef struct ICU4XStringSet ICU4XStringSet;
+typedef struct ICU4XClassSetExpression ICU4XClassSetExpression;
+typedef struct ICU4XStringSetMatcher ICU4XStringSetMatcher;
+
+typedef enum ICU4XPropertyError {
+  ICU4XPropertyError_UnknownName = 1,
+  ICU4XPropertyError_UnknownValue = 2,
+  ICU4XPropertyError_ValueNotAllowed = 3,
+  ICU4XPropertyError_ValueRequired = 4,
+  ICU4XPropertyError_DataUnavailable = 5,
+} ICU4XPropertyError;
+
+typedef enum ICU4XClassSetError {
+  ICU4XClassSetError_RangeOutOfOrder = 1,
+  ICU4XClassSetError_NegatedStrings = 2,
+  ICU4XClassSetError_MixedOperators = 3,
+} ICU4XClassSetError;
+
+typedef enum ICU4XPropertyEscapeErrorKind {
+  ICU4XPropertyEscapeErrorKind_UnknownName = 1,
+  ICU4XPropertyEscapeErrorKind_UnknownValue = 2,
+  ICU4XPropertyEscapeErrorKind_ValueNotAllowed = 3,
+  ICU4XPropertyEscapeErrorKind_ValueRequired = 4,
+  ICU4XPropertyEscapeErrorKind_DataUnavailable = 5,
+  ICU4XPropertyEscapeErrorKind_MissingOpenBrace = 6,
+  ICU4XPropertyEscapeErrorKind_MissingCloseBrace = 7,
+  ICU4XPropertyEscapeErrorKind_InvalidCharacter = 8,
+  ICU4XPropertyEscapeErrorKind_NegatedStrings = 9,
+} ICU4XPropertyEscapeErrorKind;
+
+// `offset` is the offset in the pattern of the code unit where the error
+// was found.
+typedef struct ICU4XPropertyEscapeError {
+  ICU4XPropertyEscapeErrorKind kind;
+  size_t offset;
+} ICU4XPropertyEscapeError;
+
+typedef enum ICU4XClassErrorKind {
+  // 1-9 are the values of ICU4XPropertyEscapeErrorKind.
+  ICU4XClassErrorKind_UnknownName = 1,
+  ICU4XClassErrorKind_UnknownValue = 2,
+  ICU4XClassErrorKind_ValueNotAllowed = 3,
+  ICU4XClassErrorKind_ValueRequired = 4,
+  ICU4XClassErrorKind_DataUnavailable = 5,
+  ICU4XClassErrorKind_MissingOpenBrace = 6,
+  ICU4XClassErrorKind_MissingCloseBrace = 7,
+  ICU4XClassErrorKind_InvalidCharacter = 8,
+  ICU4XClassErrorKind_NegatedStrings = 9,
+  ICU4XClassErrorKind_MissingCloseBracket = 10,
+  ICU4XClassErrorKind_InvalidEscape = 11,
+  ICU4XClassErrorKind_RangeOutOfOrder = 12,
+  ICU4XClassErrorKind_InvalidRangeEndpoint = 13,
+  ICU4XClassErrorKind_SyntaxCharacter = 14,
+  ICU4XClassErrorKind_InvalidOperation = 15,
+  ICU4XClassErrorKind_UnsupportedFlags = 16,
+} ICU4XClassErrorKind;
+
+typedef struct ICU4XClassError {
+  ICU4XClassErrorKind kind;
+  size_t offset;
+} ICU4XClassError;
+
+// A node of the prefix trie of an ICU4XStringSetMatcher. The root is the
+// first node, and the children of a node are stored together, sorted by
+// their `unit`.
+typedef struct ICU4XStringTrieNode {
+  uint16_t unit;
+  bool is_end;
+  uint32_t first_child;
+  uint32_t child_count;
+} ICU4XStringTrieNode;
+
+// A resolved property. Its contents are opaque, but stable: they do not
+// change when properties or values are added to the data, so they can be
+// stored in compiled bytecode.
+typedef struct ICU4XPropertyId {
+  uint16_t kind;
+  uint16_t index;
+} ICU4XPropertyId;
+
+typedef enum ICU4XCanonicalizeMode {
+  ICU4XCanonicalizeMode_Unicode = 0,
+  ICU4XCanonicalizeMode_Legacy = 1,
+} ICU4XCanonicalizeMode;
+
+typedef struct ICU4XRegExpFlags {
+  bool unicode;
+  bool unicode_sets;
+  bool ignore_case;
+  bool dot_all;
+} ICU4XRegExpFlags;
+
+typedef enum ICU4XClassEscape {
+  ICU4XClassEscape_Digit = 0,
+  ICU4XClassEscape_NotDigit = 1,
+  ICU4XClassEscape_Space = 2,
+  ICU4XClassEscape_NotSpace = 3,
+  ICU4XClassEscape_Word = 4,
+  ICU4XClassEscape_NotWord = 5,
+  ICU4XClassEscape_Dot = 6,
+} ICU4XClassEscape;
+
+typedef enum ICU4XPropertyKind {
+  ICU4XPropertyKind_Binary = 0,
+  ICU4XPropertyKind_Enumerated = 1,
+  ICU4XPropertyKind_BinaryOfStrings = 2,
+} ICU4XPropertyKind;
 
 ICU4XUniset* icu4x_uniset_create_for_property(const char* prop_name,
 					    const char* prop_value);
+ICU4XUniset* icu4x_uniset_try_create_for_property(const char* prop_name,
+						const char* prop_value,
+						ICU4XPropertyError* error);
+// Returns the set matched by a CharacterClassEscape or `.` in a RegExp
+// with `flags`. Without the `u` or `v` flag, the set stops at U+FFFF.
+ICU4XUniset* icu4x_uniset_create_for_class_escape(ICU4XClassEscape escape,
+						ICU4XRegExpFlags flags,
+						ICU4XPropertyError* error);
 ICU4XUniset* icu4x_uniset_complemented(ICU4XUniset* set);
+ICU4XUniset* icu4x_uniset_case_closed(ICU4XUniset* set,
+				    ICU4XCanonicalizeMode mode);
 
 size_t icu4x_uniset_get_range_count(const ICU4XUniset* set);
 uint32_t icu4x_uniset_get_range_start(const ICU4XUniset* set, size_t index);
@@ -22,6 +140,238 @@ uint32_t icu4x_uniset_get_range_end(const ICU4XUniset* set, size_t index);
 
 void icu4x_uniset_destroy(ICU4XUniset* set);
 
+// Looks up a property for a RegExp with the `v` flag, which may be a
+// property of strings like RGI_Emoji.
+ICU4XStringSet* icu4x_string_set_try_create_for_property(const char* prop_name,
+						       const char* prop_value,
+						       ICU4XPropertyError* error);
+
+// The returned set is owned by `set`, and must not be destroyed.
+const ICU4XUniset* icu4x_string_set_get_code_points(const ICU4XStringSet* set);
+
+size_t icu4x_string_set_get_string_count(const ICU4XStringSet* set);
+size_t icu4x_string_set_get_string_length(const ICU4XStringSet* set,
+					  size_t index);
+// Copies the first `capacity` UTF-16 code units of the string at `index`
+// into `buffer`, without a NUL terminator, and returns the number copied.
+// If the string is longer than `capacity`, the copy is truncated, so a
+// return value equal to `capacity` may not be the whole string: compare it
+// with `icu4x_string_set_get_string_length`. `buffer` may be null if
+// `capacity` is 0.
+size_t icu4x_string_set_copy_string(const ICU4XStringSet* set, size_t index,
+				    uint16_t* buffer, size_t capacity);
+
+void icu4x_string_set_destroy(ICU4XStringSet* set);
+
+// The catalog of supported properties. Name 0 of a property or value is
+// its canonical name, and the others are aliases. Names are UTF-8 and
+// not NUL-terminated: their length is stored in `*length`.
+size_t icu4x_property_catalog_get_property_count(void);
+ICU4XPropertyKind icu4x_property_catalog_get_property_kind(size_t property);
+size_t icu4x_property_catalog_get_name_count(size_t property);
+const char* icu4x_property_catalog_get_name(size_t property, size_t name,
+					    size_t* length);
+size_t icu4x_property_catalog_get_value_count(size_t property);
+size_t icu4x_property_catalog_get_value_name_count(size_t property,
+						   size_t value);
+const char* icu4x_property_catalog_get_value_name(size_t property,
+						  size_t value, size_t name,
+						  size_t* length);
+
+// Returns the closest valid spelling of a rejected property name, or of
+// its value, or null if there is none. The result is UTF-8 and not
+// NUL-terminated: its length is stored in `*length`.
+const char* icu4x_property_suggest(const char* prop_name,
+				   const char* prop_value, size_t* length);
+
+// Resolves a property name and optional value, and stores its ID in
+// `*id`. On failure, stores the reason in `*error` and returns false.
+bool icu4x_property_resolve(const char* prop_name, const char* prop_value,
+			    bool v_mode, ICU4XPropertyId* id,
+			    ICU4XPropertyError* error);
+bool icu4x_property_id_may_contain_strings(ICU4XPropertyId id);
+
+// Returns null for the properties of strings.
+ICU4XUniset* icu4x_uniset_create_for_property_id(ICU4XPropertyId id,
+					       ICU4XPropertyError* error);
+ICU4XStringSet* icu4x_string_set_create_for_property_id(ICU4XPropertyId id,
+						      ICU4XPropertyError* error);
+
+// Values of one code point. General_Category values are those of ICU's
+// UCharCategory, and Script values are those of ICU's UScriptCode.
+uint8_t icu4x_general_category_of(uint32_t code_point);
+uint16_t icu4x_script_of(uint32_t code_point);
+
+// Copies as many of the Script_Extensions values of a code point as fit
+// into `buffer`, and returns the number of values.
+size_t icu4x_script_extensions_of(uint32_t code_point, uint16_t* buffer,
+				  size_t capacity);
+
+// Names of General_Category and Script values. Names are UTF-8 and not
+// NUL-terminated: their length is stored in `*length`. Unknown values
+// return null.
+const char* icu4x_general_category_get_long_name(uint8_t gc, size_t* length);
+const char* icu4x_general_category_get_short_name(uint8_t gc,
+						  size_t* length);
+const char* icu4x_script_get_long_name(uint16_t script, size_t* length);
+const char* icu4x_script_get_short_name(uint16_t script, size_t* length);
+
+// ECMAScript Canonicalize for case-insensitive RegExps with the `u` or
+// `v` flag: the simple case folding of `code_point`.
+uint32_t icu4x_canonicalize_unicode(uint32_t code_point);
+
+// ECMAScript Canonicalize for case-insensitive RegExps without the `u` or
+// `v` flag, based on the full uppercase mapping of `code_unit`.
+uint16_t icu4x_canonicalize_legacy(uint16_t code_unit);
+
+// Compares two UTF-16 strings character by character using Canonicalize,
+// as a case-insensitive backreference does.
+bool icu4x_case_insensitive_equals_utf16(const uint16_t* a, size_t a_length,
+					 const uint16_t* b, size_t b_length,
+					 ICU4XCanonicalizeMode mode);
+
+// The non-trivial equivalence classes of Canonicalize in a mode, in order
+// of canonical value. `icu4x_case_class_get_code_points` copies as many
+// code points as fit into `buffer`, and returns the number of code points.
+// `buffer` may be null if `capacity` is 0.
+#define ICU4X_CASE_CLASS_MIXES_ASCII 1
+#define ICU4X_CASE_CLASS_MIXES_BMP_AND_SUPPLEMENTARY 2
+
+size_t icu4x_case_class_get_count(ICU4XCanonicalizeMode mode);
+size_t icu4x_case_class_get_code_points(ICU4XCanonicalizeMode mode,
+					size_t index, uint32_t* buffer,
+					size_t capacity);
+uint32_t icu4x_case_class_get_canonical(ICU4XCanonicalizeMode mode,
+					size_t index);
+uint32_t icu4x_case_class_get_flags(ICU4XCanonicalizeMode mode, size_t index);
+
+// IdentifierStartChar and IdentifierPartChar of ECMA-262.
+bool icu4x_is_identifier_start(uint32_t code_point);
+bool icu4x_is_identifier_part(uint32_t code_point);
+
+// Validates the source text of a capture group name, between `<` and `>`,
+// including any \u escapes.
+bool icu4x_is_valid_group_name_utf16(const uint16_t* name, size_t length);
+
+// The version of the library, the Unicode version of its data, the keys
+// of the property data it contains, and a checksum of all of its data.
+// The Unicode version is the one that the headers of the data files name
+// as their source, which the build checks they agree on. Strings are not
+// NUL-terminated: their length is stored in `*length`.
+const char* icu4x_get_crate_version(size_t* length);
+const char* icu4x_get_unicode_version(size_t* length);
+size_t icu4x_get_data_key_count(void);
+const char* icu4x_get_data_key(size_t index, size_t* length);
+uint64_t icu4x_get_data_checksum(void);
+
+// Builds the ClassSetExpression of a class in a RegExp with the `v` flag.
+// Unions, intersections and subtractions are created empty, and their
+// operands are added in order with `icu4x_class_set_expression_add_operand`.
+// Functions that take an expression or set as an operand take ownership of
+// it. A `\q{a|bc}` is a single string disjunction, where string `i` is the
+// `lengths[i]` code units at `strings[i]`, so it is an operand of an
+// intersection or subtraction on its own, unlike a union of `\q{a}` and
+// `\q{bc}`. A nested class `[…]` that is an operand of an intersection or
+// subtraction is created with `icu4x_class_set_expression_create_nested`.
+ICU4XClassSetExpression* icu4x_class_set_expression_create_character(
+	uint32_t code_point);
+ICU4XClassSetExpression* icu4x_class_set_expression_create_range(uint32_t start,
+								 uint32_t end);
+ICU4XClassSetExpression* icu4x_class_set_expression_create_string_disjunction(
+	const uint16_t* const* strings, const size_t* lengths, size_t count);
+ICU4XClassSetExpression* icu4x_class_set_expression_create_for_string_set(
+	ICU4XStringSet* set);
+ICU4XClassSetExpression* icu4x_class_set_expression_create_for_uniset(
+	ICU4XUniset* set);
+ICU4XClassSetExpression* icu4x_class_set_expression_create_union(void);
+ICU4XClassSetExpression* icu4x_class_set_expression_create_intersection(void);
+ICU4XClassSetExpression* icu4x_class_set_expression_create_subtraction(void);
+ICU4XClassSetExpression* icu4x_class_set_expression_create_nested(
+	ICU4XClassSetExpression* operand);
+ICU4XClassSetExpression* icu4x_class_set_expression_create_complement(
+	ICU4XClassSetExpression* operand);
+// Appends `operand` to a union, intersection or subtraction. Returns false
+// if `expression` is any other kind of expression. `operand` is consumed
+// even when this returns false: it is destroyed, and must not be used or
+// destroyed again.
+bool icu4x_class_set_expression_add_operand(ICU4XClassSetExpression* expression,
+					    ICU4XClassSetExpression* operand);
+
+// The MayContainStrings static semantics of the expression, which depend
+// on its syntax rather than its value.
+bool icu4x_class_set_expression_may_contain_strings(
+	const ICU4XClassSetExpression* expression);
+// Checks the early errors of the expression: a complement that may contain
+// strings (`[^\q{ab}]`, `\P{RGI_Emoji}`), an intersection or subtraction
+// with a range, union or other operation as an operand that is not in a
+// nested class (`[a&&b--c]`, `[ab&&c]`, `[a-z&&b]`), or a range out of
+// order. Returns false and stores the first error in `*error` if there is
+// one.
+bool icu4x_class_set_expression_validate(
+	const ICU4XClassSetExpression* expression, ICU4XClassSetError* error);
+
+// With `ignore_case`, the result is simple case folded, and should be
+// matched against canonicalized input.
+ICU4XStringSet* icu4x_class_set_expression_evaluate(
+	const ICU4XClassSetExpression* expression, bool ignore_case,
+	ICU4XClassSetError* error);
+void icu4x_class_set_expression_destroy(ICU4XClassSetExpression* expression);
+
+// Parses the `{Name=Value}` of a property escape, where `cursor` is the
+// offset in `pattern` just after the `\p` or `\P`, and resolves it. The
+// set is complemented if `negated` is set. On success, the number of code
+// units up to and including the `}` is stored in `*consumed`.
+ICU4XStringSet* icu4x_parse_property_escape_latin1(
+	const uint8_t* pattern, size_t length, size_t cursor, bool negated,
+	bool v_mode, size_t* consumed, ICU4XPropertyEscapeError* error);
+ICU4XStringSet* icu4x_parse_property_escape_utf16(
+	const uint16_t* pattern, size_t length, size_t cursor, bool negated,
+	bool v_mode, size_t* consumed, ICU4XPropertyEscapeError* error);
+
+// Compiles a character class of a RegExp with the `u` or `v` flag, where
+// `cursor` is the offset in `pattern` just after the `[`. On success, the
+// number of code units up to and including the `]` is stored in
+// `*consumed`. With the `i` flag, the code points are closed under case,
+// and with the `v` flag, the strings are simple case folded.
+ICU4XStringSet* icu4x_compile_class_latin1(const uint8_t* pattern,
+					   size_t length, size_t cursor,
+					   ICU4XRegExpFlags flags,
+					   size_t* consumed,
+					   ICU4XClassError* error);
+ICU4XStringSet* icu4x_compile_class_utf16(const uint16_t* pattern,
+					  size_t length, size_t cursor,
+					  ICU4XRegExpFlags flags,
+					  size_t* consumed,
+					  ICU4XClassError* error);
+
+// Prepares a set for matching as a class with the `v` flag, taking
+// ownership of it. The strings of more than one code point are tried first,
+// longest first, then the code points, then the empty string. They can be
+// emitted as alternatives in that order, or matched by walking the trie.
+// With `ignore_case`, the strings must be simple case folded, as they are
+// from `icu4x_compile_class_*`, and the input is canonicalized.
+ICU4XStringSetMatcher* icu4x_string_set_matcher_create(ICU4XStringSet* set,
+						       bool ignore_case);
+size_t icu4x_string_set_matcher_get_alternative_count(
+	const ICU4XStringSetMatcher* matcher);
+// The returned code units are owned by `matcher`, and not NUL-terminated.
+const uint16_t* icu4x_string_set_matcher_get_alternative(
+	const ICU4XStringSetMatcher* matcher, size_t index, size_t* length);
+// The returned set is owned by `matcher`, and must not be destroyed.
+const ICU4XUniset* icu4x_string_set_matcher_get_code_points(
+	const ICU4XStringSetMatcher* matcher);
+bool icu4x_string_set_matcher_matches_empty(
+	const ICU4XStringSetMatcher* matcher);
+// The returned nodes are owned by `matcher`.
+const ICU4XStringTrieNode* icu4x_string_set_matcher_get_trie(
+	const ICU4XStringSetMatcher* matcher, size_t* length);
+// Returns false if nothing matches at `pos` in `input`. Otherwise, stores
+// the length in code units of the longest match in `*matched`.
+bool icu4x_string_set_matcher_longest_match(
+	const ICU4XStringSetMatcher* matcher, const uint16_t* input,
+	size_t length, size_t pos, size_t* matched);
+void icu4x_string_set_matcher_destroy(ICU4XStringSetMatcher* matcher);
+
 #ifdef __cplusplus
 }
 #endif
@@ -16,18 +16,36 @@ struct ICU4XUnisetDeleter {
 
 class Uniset {
 private:
+  friend class ClassSetExpression;
+
   Uniset(ICU4XUniset* uniset) : inner_(uniset) {}
 
 public:
   Uniset(const char* prop_name, const char* prop_value = nullptr)
     : Uniset(icu4x_uniset_create_for_property(prop_name, prop_value)) {}
 
+  // If the property does not exist, `*error` is set to the reason.
+  Uniset(const char* prop_name, const char* prop_value,
+         ICU4XPropertyError* error)
+    : Uniset(icu4x_uniset_try_create_for_property(prop_name, prop_value,
+                                                  error)) {}
+
+  // The set matched by a CharacterClassEscape or `.` with `flags`.
+  Uniset(ICU4XClassEscape escape, ICU4XRegExpFlags flags,
+         ICU4XPropertyError* error)
+    : Uniset(icu4x_uniset_create_for_class_escape(escape, flags, error)) {}
+
   bool exists() const { return !!inner_; }
 
   void complement() {
     inner_.reset(icu4x_uniset_complemented(inner_.release()));
   }
 
+  // Adds every code point that canonicalizes like a member of the set.
+  void caseClose(ICU4XCanonicalizeMode mode) {
+    inner_.reset(icu4x_uniset_case_closed(inner_.release(), mode));
+  }
+
   size_t getRangeCount() const {
     return icu4x_uniset_get_range_count(inner_.get());
   }
@@ -40,9 +58,232 @@ public:
   }
 
 private:
+  ICU4XUniset* release() { return inner_.release(); }
+
   std::unique_ptr<ICU4XUniset, ICU4XUnisetDeleter> inner_;
 };
 
+struct ICU4XStringSetDeleter {
+  void operator()(ICU4XStringSet* s) const noexcept {
+    icu4x_string_set_destroy(s);
+  }
+};
+
+// The code points and strings of a property or class in a RegExp with the
+// `v` flag.
+class StringSet {
+private:
+  friend class ClassSetExpression;
+  friend class StringSetMatcher;
+
+  StringSet(ICU4XStringSet* set) : inner_(set) {}
+
+public:
+  // If the property does not exist, `*error` is set to the reason.
+  StringSet(const char* prop_name, const char* prop_value,
+            ICU4XPropertyError* error)
+    : inner_(icu4x_string_set_try_create_for_property(prop_name, prop_value,
+                                                      error)) {}
+
+  // Compiles a character class, where `cursor` is the offset in `pattern`
+  // just after the `[`. On success, the number of code units up to and
+  // including the `]` is stored in `*consumed`. If the class is not valid,
+  // `*error` is set to the reason and its offset.
+  StringSet(const uint8_t* pattern, size_t length, size_t cursor,
+            ICU4XRegExpFlags flags, size_t* consumed, ICU4XClassError* error)
+    : inner_(icu4x_compile_class_latin1(pattern, length, cursor, flags,
+                                        consumed, error)) {}
+  StringSet(const uint16_t* pattern, size_t length, size_t cursor,
+            ICU4XRegExpFlags flags, size_t* consumed, ICU4XClassError* error)
+    : inner_(icu4x_compile_class_utf16(pattern, length, cursor, flags,
+                                       consumed, error)) {}
+
+  bool exists() const { return !!inner_; }
+
+  size_t getRangeCount() const {
+    return icu4x_uniset_get_range_count(codePoints());
+  }
+
+  uint32_t getRangeStart(size_t index) const {
+    return icu4x_uniset_get_range_start(codePoints(), index);
+  }
+  uint32_t getRangeEnd(size_t index) const {
+    return icu4x_uniset_get_range_end(codePoints(), index);
+  }
+
+  size_t getStringCount() const {
+    return icu4x_string_set_get_string_count(inner_.get());
+  }
+
+  size_t getStringLength(size_t index) const {
+    return icu4x_string_set_get_string_length(inner_.get(), index);
+  }
+  size_t copyString(size_t index, uint16_t* buffer, size_t capacity) const {
+    return icu4x_string_set_copy_string(inner_.get(), index, buffer,
+                                        capacity);
+  }
+
+private:
+  const ICU4XUniset* codePoints() const {
+    return icu4x_string_set_get_code_points(inner_.get());
+  }
+
+  ICU4XStringSet* release() { return inner_.release(); }
+
+  std::unique_ptr<ICU4XStringSet, ICU4XStringSetDeleter> inner_;
+};
+
+struct ICU4XClassSetExpressionDeleter {
+  void operator()(ICU4XClassSetExpression* e) const noexcept {
+    icu4x_class_set_expression_destroy(e);
+  }
+};
+
+// The ClassSetExpression of a class in a RegExp with the `v` flag. The
+// functions that take an operand or a set take ownership of it, and leave
+// the argument empty.
+class ClassSetExpression {
+private:
+  ClassSetExpression(ICU4XClassSetExpression* expression)
+    : inner_(expression) {}
+
+public:
+  static ClassSetExpression createCharacter(uint32_t code_point) {
+    return icu4x_class_set_expression_create_character(code_point);
+  }
+  static ClassSetExpression createRange(uint32_t start, uint32_t end) {
+    return icu4x_class_set_expression_create_range(start, end);
+  }
+  // A `\q{…}` of `count` strings, where string `i` is the `lengths[i]` code
+  // units at `strings[i]`.
+  static ClassSetExpression createStringDisjunction(
+      const uint16_t* const* strings, const size_t* lengths, size_t count) {
+    return icu4x_class_set_expression_create_string_disjunction(
+        strings, lengths, count);
+  }
+  static ClassSetExpression createForStringSet(StringSet&& set) {
+    return icu4x_class_set_expression_create_for_string_set(set.release());
+  }
+  static ClassSetExpression createForUniset(Uniset&& set) {
+    return icu4x_class_set_expression_create_for_uniset(set.release());
+  }
+
+  // Unions, intersections and subtractions are created empty.
+  static ClassSetExpression createUnion() {
+    return icu4x_class_set_expression_create_union();
+  }
+  static ClassSetExpression createIntersection() {
+    return icu4x_class_set_expression_create_intersection();
+  }
+  static ClassSetExpression createSubtraction() {
+    return icu4x_class_set_expression_create_subtraction();
+  }
+
+  // A nested class `[…]`, for an operand of an intersection or subtraction.
+  static ClassSetExpression createNested(ClassSetExpression&& operand) {
+    return icu4x_class_set_expression_create_nested(operand.inner_.release());
+  }
+  static ClassSetExpression createComplement(ClassSetExpression&& operand) {
+    return icu4x_class_set_expression_create_complement(
+        operand.inner_.release());
+  }
+
+  bool exists() const { return !!inner_; }
+
+  // Appends `operand` to a union, intersection or subtraction. Returns false
+  // if this is any other kind of expression. `operand` is consumed either
+  // way.
+  bool addOperand(ClassSetExpression&& operand) {
+    return icu4x_class_set_expression_add_operand(inner_.get(),
+                                                  operand.inner_.release());
+  }
+
+  bool mayContainStrings() const {
+    return icu4x_class_set_expression_may_contain_strings(inner_.get());
+  }
+
+  // If the expression has an early error, `*error` is set to it.
+  bool validate(ICU4XClassSetError* error) const {
+    return icu4x_class_set_expression_validate(inner_.get(), error);
+  }
+
+  // If the expression cannot be evaluated, `*error` is set to the reason,
+  // and the result does not exist.
+  StringSet evaluate(bool ignore_case, ICU4XClassSetError* error) const {
+    return icu4x_class_set_expression_evaluate(inner_.get(), ignore_case,
+                                               error);
+  }
+
+private:
+  std::unique_ptr<ICU4XClassSetExpression, ICU4XClassSetExpressionDeleter>
+      inner_;
+};
+
+struct ICU4XStringSetMatcherDeleter {
+  void operator()(ICU4XStringSetMatcher* m) const noexcept {
+    icu4x_string_set_matcher_destroy(m);
+  }
+};
+
+// A set prepared for matching as a class with the `v` flag. The strings of
+// more than one code point are tried first, longest first, then the code
+// points, then the empty string.
+class StringSetMatcher {
+public:
+  // Takes ownership of `set`, and leaves it empty. With `ignore_case`, the
+  // strings of `set` must be simple case folded.
+  StringSetMatcher(StringSet&& set, bool ignore_case)
+    : inner_(icu4x_string_set_matcher_create(set.release(), ignore_case)) {}
+
+  bool exists() const { return !!inner_; }
+
+  size_t getAlternativeCount() const {
+    return icu4x_string_set_matcher_get_alternative_count(inner_.get());
+  }
+
+  // The returned code units are owned by the matcher, and not
+  // NUL-terminated.
+  const uint16_t* getAlternative(size_t index, size_t* length) const {
+    return icu4x_string_set_matcher_get_alternative(inner_.get(), index,
+                                                    length);
+  }
+
+  size_t getRangeCount() const {
+    return icu4x_uniset_get_range_count(codePoints());
+  }
+
+  uint32_t getRangeStart(size_t index) const {
+    return icu4x_uniset_get_range_start(codePoints(), index);
+  }
+  uint32_t getRangeEnd(size_t index) const {
+    return icu4x_uniset_get_range_end(codePoints(), index);
+  }
+
+  bool matchesEmpty() const {
+    return icu4x_string_set_matcher_matches_empty(inner_.get());
+  }
+
+  // The returned nodes are owned by the matcher.
+  const ICU4XStringTrieNode* getTrie(size_t* length) const {
+    return icu4x_string_set_matcher_get_trie(inner_.get(), length);
+  }
+
+  // Returns false if nothing matches at `pos` in `input`. Otherwise, stores
+  // the length in code units of the longest match in `*matched`.
+  bool longestMatch(const uint16_t* input, size_t length, size_t pos,
+                    size_t* matched) const {
+    return icu4x_string_set_matcher_longest_match(inner_.get(), input, length,
+                                                  pos, matched);
+  }
+
+private:
+  const ICU4XUniset* codePoints() const {
+    return icu4x_string_set_matcher_get_code_points(inner_.get());
+  }
+
+  std::unique_ptr<ICU4XStringSetMatcher, ICU4XStringSetMatcherDeleter> inner_;
+};
+
 }  // namespace icu4x
 
 #endif  // ICU4X_UNISET_HPP
@@ -0,0 +1,452 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! The [Canonicalize](https://tc39.es/ecma262/multipage/text-processing.html#sec-runtime-semantics-canonicalize-ch)
+//! algorithm used by case-insensitive RegExps.
+
+use crate::ICU4XUniset;
+use icu_uniset::{UnicodeSet, UnicodeSetBuilder};
+use once_cell::sync::OnceCell;
+
+// Generated by build.rs from data/CaseFolding.txt.
+include!(concat!(env!("OUT_DIR"), "/case_folding.rs"));
+
+// Generated by build.rs from data/UppercaseMapping.txt.
+include!(concat!(env!("OUT_DIR"), "/uppercase_mapping.rs"));
+
+/// Canonicalize(rer, ch) when rer.[[Unicode]] or rer.[[UnicodeSets]] is
+/// true: if CaseFolding.txt provides a simple or common case folding
+/// mapping for `code_point`, returns the result of applying that mapping.
+/// Otherwise, returns `code_point`.
+pub fn canonicalize_unicode(code_point: u32) -> u32 {
+    CASE_FOLDING
+        .binary_search_by_key(&code_point, |&(from, _)| from)
+        .map_or(code_point, |i| CASE_FOLDING[i].1)
+}
+
+/// Canonicalize(rer, ch) when neither rer.[[Unicode]] nor
+/// rer.[[UnicodeSets]] is true: `code_unit` is mapped with the full
+/// toUppercase mapping, unless the result is not a single code unit or
+/// would map a non-ASCII character to ASCII.
+pub fn canonicalize_legacy(code_unit: u16) -> u16 {
+    // Steps 2-3.
+    let ch = code_unit as u32;
+    let upper = match UPPERCASE_MAPPING.binary_search_by_key(&ch, |&(from, _)| from) {
+        Ok(i) => UPPERCASE_MAPPING[i].1,
+        Err(_) => return code_unit,
+    };
+
+    // Steps 4-5.
+    let cu = match upper {
+        &[cu] if cu <= 0xFFFF => cu as u16,
+        _ => return code_unit,
+    };
+
+    // Step 6.
+    if code_unit >= 128 && cu < 128 {
+        return code_unit;
+    }
+
+    // Step 7.
+    cu
+}
+
+/// Which Canonicalize a case-insensitive RegExp uses.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CanonicalizeMode {
+    /// [`canonicalize_unicode`], with the `u` or `v` flag.
+    Unicode = 0,
+    /// [`canonicalize_legacy`], without the `u` or `v` flag.
+    Legacy = 1,
+}
+
+/// Canonicalize in the given mode. In legacy mode, code points beyond
+/// U+FFFF are returned unchanged.
+pub fn canonicalize(code_point: u32, mode: CanonicalizeMode) -> u32 {
+    match mode {
+        CanonicalizeMode::Unicode => canonicalize_unicode(code_point),
+        CanonicalizeMode::Legacy if code_point <= 0xFFFF => {
+            canonicalize_legacy(code_point as u16) as u32
+        }
+        CanonicalizeMode::Legacy => code_point,
+    }
+}
+
+/// A non-trivial equivalence class of Canonicalize: a canonical value
+/// and the code points that canonicalize to it.
+#[derive(Debug)]
+pub struct CaseClass {
+    canonical: u32,
+    code_points: Vec<u32>,
+}
+
+impl CaseClass {
+    /// The value every code point of the class canonicalizes to.
+    pub fn canonical(&self) -> u32 {
+        self.canonical
+    }
+
+    /// The code points of the class, including the canonical value, in
+    /// ascending order. There are at least two.
+    pub fn code_points(&self) -> &[u32] {
+        &self.code_points
+    }
+
+    /// Whether the class contains both ASCII and non-ASCII code points,
+    /// like `k`, `K` and U+212A KELVIN SIGN.
+    pub fn mixes_ascii(&self) -> bool {
+        self.code_points[0] < 0x80 && self.code_points[self.code_points.len() - 1] >= 0x80
+    }
+
+    /// Whether the class contains both BMP and supplementary code points,
+    /// which have different lengths in UTF-16.
+    pub fn mixes_bmp_and_supplementary(&self) -> bool {
+        self.code_points[0] <= 0xFFFF && self.code_points[self.code_points.len() - 1] > 0xFFFF
+    }
+}
+
+/// The code points that Canonicalize does not map to themselves, grouped
+/// into classes of code points with the same canonical value.
+struct CaseClasses {
+    /// Every code point in a class, with the index of its class, sorted
+    /// by code point.
+    members: Vec<(u32, usize)>,
+    classes: Vec<CaseClass>,
+}
+
+impl CaseClasses {
+    fn new(mappings: impl Iterator<Item = (u32, u32)>) -> Self {
+        let mut by_canonical: Vec<(u32, u32)> = mappings.map(|(from, to)| (to, from)).collect();
+        by_canonical.sort_unstable();
+
+        let mut classes: Vec<CaseClass> = vec![];
+        for (canonical, from) in by_canonical {
+            if classes
+                .last()
+                .is_none_or(|class| class.canonical != canonical)
+            {
+                classes.push(CaseClass {
+                    canonical,
+                    code_points: vec![canonical],
+                });
+            }
+            classes.last_mut().unwrap().code_points.push(from);
+        }
+
+        let mut members = vec![];
+        for (i, class) in classes.iter_mut().enumerate() {
+            class.code_points.sort_unstable();
+            members.extend(class.code_points.iter().map(|&code_point| (code_point, i)));
+        }
+        members.sort_unstable();
+        CaseClasses { members, classes }
+    }
+}
+
+fn get_case_classes(mode: CanonicalizeMode) -> &'static CaseClasses {
+    static UNICODE: OnceCell<CaseClasses> = OnceCell::new();
+    static LEGACY: OnceCell<CaseClasses> = OnceCell::new();
+    match mode {
+        CanonicalizeMode::Unicode => {
+            UNICODE.get_or_init(|| CaseClasses::new(CASE_FOLDING.iter().copied()))
+        }
+        CanonicalizeMode::Legacy => LEGACY.get_or_init(|| {
+            CaseClasses::new((0..=0xFFFF).filter_map(|code_unit| {
+                let canonical = canonicalize_legacy(code_unit);
+                if canonical != code_unit {
+                    Some((code_unit as u32, canonical as u32))
+                } else {
+                    None
+                }
+            }))
+        }),
+    }
+}
+
+/// Returns the set of code points that canonicalize to the same value as
+/// a member of `set`, like ICU's `closeOver(USET_CASE_INSENSITIVE)` but
+/// with ECMAScript's Canonicalize for `mode`.
+pub fn case_close(set: &ICU4XUniset, mode: CanonicalizeMode) -> ICU4XUniset {
+    let case_classes = get_case_classes(mode);
+    let set = set.get();
+
+    let mut builder = UnicodeSetBuilder::new();
+    builder.add_set(set);
+    for range in (0..set.get_range_count()).filter_map(|i| set.get_nth_range(i)) {
+        let start = case_classes
+            .members
+            .partition_point(|&(code_point, _)| code_point < *range.start());
+        let in_range = case_classes.members[start..]
+            .iter()
+            .take_while(|&&(code_point, _)| code_point <= *range.end());
+        for &(_, class) in in_range {
+            // Canonicalize never maps surrogates.
+            for c in case_classes.classes[class]
+                .code_points
+                .iter()
+                .filter_map(|&code_point| std::char::from_u32(code_point))
+            {
+                builder.add_char(c);
+            }
+        }
+    }
+    builder.build().into()
+}
+
+/// Returns every non-trivial equivalence class of Canonicalize in
+/// `mode`, in order of canonical value.
+pub fn case_classes(mode: CanonicalizeMode) -> &'static [CaseClass] {
+    &get_case_classes(mode).classes
+}
+
+/// The MaybeSimpleCaseFolding of a class in a RegExp with the `v` and `i`
+/// flags: maps each code point of `set` with [`canonicalize_unicode`].
+pub(crate) fn simple_case_fold(set: &ICU4XUniset) -> ICU4XUniset {
+    let set = set.get();
+    let mut builder = UnicodeSetBuilder::new();
+    builder.add_set(set);
+    for range in (0..set.get_range_count()).filter_map(|i| set.get_nth_range(i)) {
+        let start = CASE_FOLDING.partition_point(|&(from, _)| from < *range.start());
+        let in_range = CASE_FOLDING[start..]
+            .iter()
+            .take_while(|&&(from, _)| from <= *range.end());
+        // Folded code points never fold again, so the order of these
+        // doesn't matter. Surrogates are never folded.
+        for &(from, to) in in_range {
+            if let (Some(from), Some(to)) = (std::char::from_u32(from), std::char::from_u32(to)) {
+                builder.remove_range(&(from..=from));
+                builder.add_char(to);
+            }
+        }
+    }
+    builder.build().into()
+}
+
+/// The AllCharacters of a RegExp with the `v` and `i` flags: the code
+/// points that [`canonicalize_unicode`] maps to themselves.
+pub(crate) fn simple_case_folded_characters() -> ICU4XUniset {
+    let mut builder = UnicodeSetBuilder::new();
+    builder.add_set(&UnicodeSet::all());
+    for c in CASE_FOLDING
+        .iter()
+        .filter_map(|&(from, _)| std::char::from_u32(from))
+    {
+        builder.remove_range(&(c..=c));
+    }
+    builder.build().into()
+}
+
+/// Whether two characters canonicalize to the same value in `mode`.
+fn case_insensitive_equals(a: u32, b: u32, mode: CanonicalizeMode) -> bool {
+    if a == b {
+        return true;
+    }
+    // Both modes map ASCII letters to one case, and leave other ASCII
+    // characters alone.
+    if a < 0x80 && b < 0x80 {
+        return (a as u8).eq_ignore_ascii_case(&(b as u8));
+    }
+    canonicalize(a, mode) == canonicalize(b, mode)
+}
+
+/// Compares two UTF-16 strings character by character using
+/// Canonicalize, as a case-insensitive backreference does. In Unicode
+/// mode, surrogate pairs are compared as code points, and lone
+/// surrogates as themselves. In legacy mode, code units are compared.
+pub fn case_insensitive_equals_utf16(a: &[u16], b: &[u16], mode: CanonicalizeMode) -> bool {
+    match mode {
+        CanonicalizeMode::Legacy => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(&a, &b)| case_insensitive_equals(a as u32, b as u32, mode))
+        }
+        CanonicalizeMode::Unicode => {
+            fn code_points(s: &[u16]) -> impl Iterator<Item = u32> + '_ {
+                std::char::decode_utf16(s.iter().copied())
+                    .map(|c| c.map_or_else(|e| e.unpaired_surrogate() as u32, |c| c as u32))
+            }
+            let (mut a, mut b) = (code_points(a), code_points(b));
+            loop {
+                match (a.next(), b.next()) {
+                    (None, None) => return true,
+                    (Some(a), Some(b)) if case_insensitive_equals(a, b, mode) => {}
+                    _ => return false,
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_canonicalize_unicode() {
+    assert_eq!(canonicalize_unicode('A' as u32), 'a' as u32);
+    assert_eq!(canonicalize_unicode('a' as u32), 'a' as u32);
+    assert_eq!(canonicalize_unicode(0x212A), 'k' as u32); // KELVIN SIGN
+    assert_eq!(canonicalize_unicode(0x03C2), 0x03C3); // GREEK SMALL LETTER FINAL SIGMA
+    assert_eq!(canonicalize_unicode(0x1E9E), 0x00DF); // LATIN CAPITAL LETTER SHARP S (S)
+
+    // Full (F) and Turkic (T) mappings are not used.
+    assert_eq!(canonicalize_unicode(0x00DF), 0x00DF);
+    assert_eq!(canonicalize_unicode(0x0130), 0x0130);
+    assert_eq!(canonicalize_unicode(0xD800), 0xD800);
+
+    assert!(CASE_FOLDING.windows(2).all(|w| w[0].0 < w[1].0));
+    for code_point in 0..=0x10FFFF {
+        let folded = canonicalize_unicode(code_point);
+        assert_eq!(canonicalize_unicode(folded), folded, "U+{:04X}", code_point);
+    }
+}
+
+#[test]
+fn test_canonicalize_legacy() {
+    assert_eq!(canonicalize_legacy('a' as u16), 'A' as u16);
+    assert_eq!(canonicalize_legacy('A' as u16), 'A' as u16);
+    assert_eq!(canonicalize_legacy(0x00B5), 0x039C); // MICRO SIGN
+    assert_eq!(canonicalize_legacy(0x00FF), 0x0178); // LATIN SMALL LETTER Y WITH DIAERESIS
+
+    // The uppercase of U+00DF LATIN SMALL LETTER SHARP S is "SS".
+    assert_eq!(canonicalize_legacy(0x00DF), 0x00DF);
+    // Non-ASCII characters do not map to ASCII.
+    assert_eq!(canonicalize_legacy(0x017F), 0x017F); // LATIN SMALL LETTER LONG S
+    assert_eq!(canonicalize_legacy(0x0131), 0x0131); // LATIN SMALL LETTER DOTLESS I
+    assert_eq!(canonicalize_legacy(0x212A), 0x212A); // KELVIN SIGN
+    assert_eq!(canonicalize_legacy(0xD800), 0xD800);
+
+    assert!(UPPERCASE_MAPPING.windows(2).all(|w| w[0].0 < w[1].0));
+    for code_unit in 0..=0xFFFF {
+        let canonical = canonicalize_legacy(code_unit);
+        assert_eq!(
+            canonicalize_legacy(canonical),
+            canonical,
+            "U+{:04X}",
+            code_unit
+        );
+    }
+}
+
+#[test]
+fn test_case_close() {
+    let check = |set: ICU4XUniset, mode| {
+        let mut canonical = std::collections::HashSet::new();
+        for i in 0..set.get().get_range_count() {
+            for code_point in set.get().get_nth_range(i).unwrap() {
+                canonical.insert(canonicalize(code_point, mode));
+            }
+        }
+        let mut builder = UnicodeSetBuilder::new();
+        builder.add_set(set.get());
+        for c in (0..=0x10FFFF).filter_map(std::char::from_u32) {
+            if canonical.contains(&canonicalize(c as u32, mode)) {
+                builder.add_char(c);
+            }
+        }
+        assert_eq!(
+            case_close(&set, mode).get().get_inversion_list(),
+            builder.build().get_inversion_list()
+        );
+    };
+
+    let small = || {
+        let mut builder = UnicodeSetBuilder::new();
+        builder.add_range(&('a'..='f'));
+        builder.add_char('k');
+        builder.add_char('\u{00DF}'); // LATIN SMALL LETTER SHARP S
+        builder.add_char('\u{03C3}'); // GREEK SMALL LETTER SIGMA
+        builder.add_char('\u{10428}'); // DESERET SMALL LETTER LONG I
+        ICU4XUniset::from(builder.build())
+    };
+    let upper = || crate::get_unicode_set("Lu", None).unwrap();
+
+    for &mode in [CanonicalizeMode::Unicode, CanonicalizeMode::Legacy].iter() {
+        check(small(), mode);
+        check(upper(), mode);
+    }
+
+    let closed = case_close(&small(), CanonicalizeMode::Unicode);
+    assert!(closed.get().contains('\u{212A}')); // KELVIN SIGN
+    assert!(closed.get().contains('\u{1E9E}')); // LATIN CAPITAL LETTER SHARP S
+    assert!(closed.get().contains('\u{03C2}')); // GREEK SMALL LETTER FINAL SIGMA
+    let closed = case_close(&small(), CanonicalizeMode::Legacy);
+    assert!(!closed.get().contains('\u{212A}'));
+    assert!(closed.get().contains('K'));
+}
+
+#[test]
+fn test_case_classes() {
+    for &mode in [CanonicalizeMode::Unicode, CanonicalizeMode::Legacy].iter() {
+        let mut members = 0;
+        for class in case_classes(mode) {
+            assert!(class.code_points().len() >= 2);
+            assert!(class.code_points().windows(2).all(|w| w[0] < w[1]));
+            for &code_point in class.code_points() {
+                assert_eq!(canonicalize(code_point, mode), class.canonical());
+            }
+            members += class.code_points().len();
+        }
+        let changed = (0..=0x10FFFF)
+            .filter(|&code_point| canonicalize(code_point, mode) != code_point)
+            .count();
+        assert_eq!(members, changed + case_classes(mode).len());
+    }
+
+    let k = |mode| {
+        case_classes(mode)
+            .iter()
+            .find(|class| class.code_points().contains(&('k' as u32)))
+            .unwrap()
+    };
+    assert_eq!(
+        k(CanonicalizeMode::Unicode).code_points(),
+        [0x4B, 0x6B, 0x212A]
+    );
+    assert!(k(CanonicalizeMode::Unicode).mixes_ascii());
+    assert_eq!(k(CanonicalizeMode::Legacy).code_points(), [0x4B, 0x6B]);
+    assert!(!case_classes(CanonicalizeMode::Legacy)
+        .iter()
+        .any(|class| class.mixes_ascii()));
+    assert!(!case_classes(CanonicalizeMode::Legacy)
+        .iter()
+        .any(|class| class.mixes_bmp_and_supplementary()));
+}
+
+#[test]
+fn test_case_insensitive_equals_utf16() {
+    let equals = |a: &str, b: &str, mode| {
+        let a: Vec<u16> = a.encode_utf16().collect();
+        let b: Vec<u16> = b.encode_utf16().collect();
+        case_insensitive_equals_utf16(&a, &b, mode)
+    };
+    for &mode in [CanonicalizeMode::Unicode, CanonicalizeMode::Legacy].iter() {
+        assert!(equals("", "", mode));
+        assert!(equals("aB1", "Ab1", mode));
+        assert!(equals("\u{03C3}", "\u{03A3}", mode)); // GREEK SIGMA
+        assert!(!equals("a", "b", mode));
+        assert!(!equals("ab", "a", mode));
+        assert!(!equals("@", "`", mode));
+    }
+
+    let unicode = |a, b| equals(a, b, CanonicalizeMode::Unicode);
+    let legacy = |a, b| equals(a, b, CanonicalizeMode::Legacy);
+    assert!(unicode("k", "\u{212A}")); // KELVIN SIGN
+    assert!(!legacy("k", "\u{212A}"));
+    assert!(unicode("\u{00DF}", "\u{1E9E}")); // SHARP S
+    assert!(!legacy("\u{00DF}", "\u{1E9E}"));
+    // DESERET CAPITAL LETTER LONG I and DESERET SMALL LETTER LONG I
+    assert!(unicode("\u{10400}", "\u{10428}"));
+    assert!(!legacy("\u{10400}", "\u{10428}"));
+
+    // Lone surrogates only match themselves.
+    let lone = [0xD801];
+    assert!(case_insensitive_equals_utf16(
+        &lone,
+        &lone,
+        CanonicalizeMode::Unicode
+    ));
+    assert!(!case_insensitive_equals_utf16(
+        &lone,
+        &[0xD802],
+        CanonicalizeMode::Unicode
+    ));
+}
@@ -0,0 +1,173 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! A catalog of the supported properties, built from the same alias
+//! tables as property lookup.
+
+use crate::tables::{
+    EnumeratedProperty, BINARY_PROPERTIES, ENUMERATED_PROPERTIES, GENERAL_CATEGORIES, SCRIPTS,
+    SPECIAL_BINARY_PROPERTIES, STRING_PROPERTIES,
+};
+use once_cell::sync::OnceCell;
+
+/// The kind of a property, which determines how it may be used in a
+/// property escape.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PropertyKind {
+    /// Used alone, like `\p{Alphabetic}`.
+    Binary = 0,
+    /// Used with a value, like `\p{Script=Greek}`.
+    Enumerated = 1,
+    /// Used alone, like `\p{RGI_Emoji}`, but only with the `v` flag.
+    BinaryOfStrings = 2,
+}
+
+/// A supported property value.
+#[derive(Debug)]
+pub struct PropertyValueInfo {
+    names: &'static [&'static str],
+}
+
+impl PropertyValueInfo {
+    /// The canonical long name of the value.
+    pub fn name(&self) -> &'static str {
+        self.names[0]
+    }
+
+    /// The other names of the value.
+    pub fn aliases(&self) -> &'static [&'static str] {
+        &self.names[1..]
+    }
+
+    pub(crate) fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+}
+
+/// A supported property.
+#[derive(Debug)]
+pub struct PropertyInfo {
+    names: &'static [&'static str],
+    kind: PropertyKind,
+    values: Vec<PropertyValueInfo>,
+}
+
+impl PropertyInfo {
+    /// The canonical long name of the property.
+    pub fn name(&self) -> &'static str {
+        self.names[0]
+    }
+
+    /// The other names of the property.
+    pub fn aliases(&self) -> &'static [&'static str] {
+        &self.names[1..]
+    }
+
+    pub fn kind(&self) -> PropertyKind {
+        self.kind
+    }
+
+    /// The values of an enumerated property. General_Category values
+    /// may also be used alone, like `\p{Lu}`.
+    pub fn values(&self) -> &[PropertyValueInfo] {
+        &self.values
+    }
+
+    pub(crate) fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+}
+
+/// Returns every supported property: the enumerated properties, then
+/// the binary properties, then the properties of strings.
+pub fn properties() -> &'static [PropertyInfo] {
+    static CATALOG: OnceCell<Vec<PropertyInfo>> = OnceCell::new();
+    CATALOG.get_or_init(|| {
+        let mut catalog = vec![];
+        for &(names, prop) in ENUMERATED_PROPERTIES {
+            let values = match prop {
+                EnumeratedProperty::GeneralCategory => GENERAL_CATEGORIES
+                    .iter()
+                    .map(|&(_, names, _)| PropertyValueInfo { names })
+                    .collect(),
+                EnumeratedProperty::Script | EnumeratedProperty::ScriptExtensions => SCRIPTS
+                    .iter()
+                    .map(|&(_, names, _)| PropertyValueInfo { names })
+                    .collect(),
+            };
+            catalog.push(PropertyInfo {
+                names,
+                kind: PropertyKind::Enumerated,
+                values,
+            });
+        }
+
+        let binary_names = BINARY_PROPERTIES
+            .iter()
+            .map(|&(_, names, _)| names)
+            .chain(SPECIAL_BINARY_PROPERTIES.iter().map(|&(names, _)| names));
+        for names in binary_names {
+            catalog.push(PropertyInfo {
+                names,
+                kind: PropertyKind::Binary,
+                values: vec![],
+            });
+        }
+
+        for &(names, _) in STRING_PROPERTIES {
+            catalog.push(PropertyInfo {
+                names,
+                kind: PropertyKind::BinaryOfStrings,
+                values: vec![],
+            });
+        }
+        catalog
+    })
+}
+
+#[test]
+fn test_catalog_matches_lookup() {
+    use crate::{get_unicode_set, try_get_string_set_with_mode, MatchingMode};
+
+    let properties = properties();
+    assert!(properties.iter().any(|p| p.name() == "Script_Extensions"
+        && p.aliases() == ["scx"]
+        && p.kind() == PropertyKind::Enumerated));
+    assert!(properties
+        .iter()
+        .any(|p| p.name() == "White_Space" && p.aliases() == ["space"]));
+
+    for prop in properties {
+        for &name in prop.names() {
+            match prop.kind() {
+                PropertyKind::Binary => {
+                    assert!(get_unicode_set(name, None).is_some(), "{}", name);
+                }
+                PropertyKind::Enumerated => {
+                    assert!(!prop.values().is_empty());
+                    let value = prop.values()[0].name();
+                    assert!(get_unicode_set(name, Some(value)).is_some(), "{}", name);
+                }
+                PropertyKind::BinaryOfStrings => {
+                    assert!(get_unicode_set(name, None).is_none(), "{}", name);
+                    assert!(
+                        try_get_string_set_with_mode(name, None, MatchingMode::Ecma262).is_ok(),
+                        "{}",
+                        name
+                    );
+                }
+            }
+        }
+        for value in prop.values() {
+            for &value_name in value.names() {
+                assert!(
+                    get_unicode_set(prop.name(), Some(value_name)).is_some(),
+                    "{}={}",
+                    prop.name(),
+                    value_name
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,724 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! Compiles the
+//! [CharacterClass](https://tc39.es/ecma262/multipage/text-processing.html#prod-CharacterClass)
+//! of a RegExp with the `u` or `v` flag into a set, reporting the early
+//! errors of the specification.
+
+use crate::class_escape::{class_escape_set, ClassEscape, RegExpFlags};
+use crate::class_set::ClassSetExpression;
+use crate::property_escape::parse_property_escape;
+use crate::{case_close, CanonicalizeMode, ClassError, ClassErrorKind, ICU4XStringSet};
+use icu_uniset::UnicodeSetBuilder;
+
+/// A parsed ClassAtom or ClassSetOperand.
+enum Atom {
+    /// A single character, which can be the end of a range.
+    Character(u32),
+    /// A class escape, nested class or `\q{…}`.
+    Operand(ClassSetExpression),
+}
+
+impl Atom {
+    fn into_expression(self) -> ClassSetExpression {
+        match self {
+            Atom::Character(c) => ClassSetExpression::Character(c),
+            Atom::Operand(operand) => operand,
+        }
+    }
+}
+
+fn is_ascii(unit: u32, f: fn(&u8) -> bool) -> bool {
+    unit < 0x80 && f(&(unit as u8))
+}
+
+/// ClassSetSyntaxCharacter.
+fn is_class_set_syntax_character(unit: u32) -> bool {
+    is_ascii(unit, |&c| b"()[]{}/-\\|".contains(&c))
+}
+
+/// ClassSetReservedPunctuator.
+fn is_class_set_reserved_punctuator(unit: u32) -> bool {
+    is_ascii(unit, |&c| b"&-!#%,:;<=>@`~".contains(&c))
+}
+
+/// The characters that form a ClassSetReservedDoublePunctuator when
+/// doubled.
+fn is_double_punctuator_character(unit: u32) -> bool {
+    is_ascii(unit, |&c| b"&!#$%*+,.:;<=>?@^`~".contains(&c))
+}
+
+fn push_utf16(string: &mut Vec<u16>, code_point: u32) {
+    if code_point > 0xFFFF {
+        let offset = code_point - 0x10000;
+        string.push(0xD800 + (offset >> 10) as u16);
+        string.push(0xDC00 + (offset & 0x3FF) as u16);
+    } else {
+        string.push(code_point as u16);
+    }
+}
+
+struct Parser<'a, T> {
+    pattern: &'a [T],
+    pos: usize,
+    flags: RegExpFlags,
+}
+
+impl<'a, T: Copy + Into<u32>> Parser<'a, T> {
+    fn peek_at(&self, n: usize) -> Option<u32> {
+        self.pattern.get(self.pos + n).map(|&unit| unit.into())
+    }
+
+    fn peek(&self) -> Option<u32> {
+        self.peek_at(0)
+    }
+
+    fn at(&self, c: char) -> bool {
+        self.peek() == Some(c as u32)
+    }
+
+    fn at_double(&self, c: char) -> bool {
+        self.at(c) && self.peek_at(1) == Some(c as u32)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.at(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn error(&self, kind: ClassErrorKind, offset: usize) -> ClassError {
+        ClassError { kind, offset }
+    }
+
+    /// The error at the current position, which is MissingCloseBracket at
+    /// the end of the pattern.
+    fn error_here(&self, kind: ClassErrorKind) -> ClassError {
+        match self.peek() {
+            Some(_) => self.error(kind, self.pos),
+            None => self.error(ClassErrorKind::MissingCloseBracket, self.pos),
+        }
+    }
+
+    fn hex_digit_at(&self, n: usize) -> Option<u32> {
+        self.peek_at(n)
+            .and_then(std::char::from_u32)
+            .and_then(|c| c.to_digit(16))
+    }
+
+    fn parse_hex4(&mut self) -> Option<u32> {
+        let mut value = 0;
+        for n in 0..4 {
+            value = value * 16 + self.hex_digit_at(n)?;
+        }
+        self.pos += 4;
+        Some(value)
+    }
+
+    /// Reads a SourceCharacter, which may be a surrogate pair.
+    fn parse_source_character(&mut self) -> Option<u32> {
+        let unit = self.peek()?;
+        self.pos += 1;
+        if (0xD800..0xDC00).contains(&unit) {
+            if let Some(trail) = self.peek().filter(|trail| (0xDC00..0xE000).contains(trail)) {
+                self.pos += 1;
+                return Some(0x10000 + ((unit - 0xD800) << 10) + (trail - 0xDC00));
+            }
+        }
+        Some(unit)
+    }
+
+    /// Parses the rest of a RegExpUnicodeEscapeSequence[+UnicodeMode],
+    /// after the `u`.
+    fn parse_unicode_escape(&mut self) -> Option<u32> {
+        if self.eat('{') {
+            let start = self.pos;
+            let mut value = 0;
+            while let Some(digit) = self.hex_digit_at(0) {
+                value = value * 16 + digit;
+                if value > 0x10FFFF {
+                    return None;
+                }
+                self.pos += 1;
+            }
+            return if self.pos > start && self.eat('}') {
+                Some(value)
+            } else {
+                None
+            };
+        }
+
+        let lead = self.parse_hex4()?;
+        if (0xD800..0xDC00).contains(&lead) && self.at('\\') && self.peek_at(1) == Some('u' as u32)
+        {
+            let saved = self.pos;
+            self.pos += 2;
+            match self.parse_hex4() {
+                Some(trail) if (0xDC00..0xE000).contains(&trail) => {
+                    return Some(0x10000 + ((lead - 0xD800) << 10) + (trail - 0xDC00));
+                }
+                _ => self.pos = saved,
+            }
+        }
+        Some(lead)
+    }
+
+    /// Parses a CharacterEscape[+UnicodeMode], after the `\`.
+    fn parse_character_escape(&mut self) -> Option<u32> {
+        let c = std::char::from_u32(self.peek()?)?;
+        let value = match c {
+            'f' => 0x0C,
+            'n' => 0x0A,
+            'r' => 0x0D,
+            't' => 0x09,
+            'v' => 0x0B,
+            'c' => {
+                let letter = self
+                    .peek_at(1)
+                    .filter(|&unit| is_ascii(unit, u8::is_ascii_alphabetic))?;
+                self.pos += 2;
+                return Some(letter % 32);
+            }
+            '0' => {
+                if self
+                    .peek_at(1)
+                    .is_some_and(|unit| is_ascii(unit, u8::is_ascii_digit))
+                {
+                    return None;
+                }
+                0
+            }
+            'x' => {
+                let value = self.hex_digit_at(1)? * 16 + self.hex_digit_at(2)?;
+                self.pos += 3;
+                return Some(value);
+            }
+            'u' => {
+                self.pos += 1;
+                return self.parse_unicode_escape();
+            }
+            // IdentityEscape[+UnicodeMode]
+            '^' | '$' | '\\' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|'
+            | '/' => c as u32,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(value)
+    }
+
+    /// Parses a CharacterClassEscape, after the `\`, if there is one.
+    /// Complemented escapes are returned as a
+    /// [`ClassSetExpression::Complement`], which the `v` flag requires to
+    /// be taken after case folding.
+    fn parse_class_escape(&mut self) -> Result<Option<ClassSetExpression>, ClassError> {
+        let start = self.pos - 1;
+        let c = match self.peek().and_then(std::char::from_u32) {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+        let (escape, complement) = match c {
+            'd' => (ClassEscape::Digit, false),
+            'D' => (ClassEscape::Digit, true),
+            's' => (ClassEscape::Space, false),
+            'S' => (ClassEscape::Space, true),
+            'w' => (ClassEscape::Word, false),
+            'W' => (ClassEscape::Word, true),
+            'p' | 'P' => {
+                let (set, length) = parse_property_escape(
+                    self.pattern,
+                    self.pos + 1,
+                    false,
+                    self.flags.unicode_sets,
+                )
+                .map_err(|e| self.error(e.kind.into(), e.offset))?;
+                if c == 'P' && set.has_strings() {
+                    return Err(self.error(ClassErrorKind::NegatedStrings, self.pos + 2));
+                }
+                self.pos += 1 + length;
+                let set = ClassSetExpression::Set(set);
+                return Ok(Some(if c == 'P' {
+                    ClassSetExpression::Complement(Box::new(set))
+                } else {
+                    set
+                }));
+            }
+            _ => return Ok(None),
+        };
+        self.pos += 1;
+        let set = class_escape_set(escape, self.flags)
+            .map_err(|e| self.error(ClassErrorKind::from(e), start))?;
+        let set = ClassSetExpression::Set(set.into());
+        Ok(Some(if complement {
+            ClassSetExpression::Complement(Box::new(set))
+        } else {
+            set
+        }))
+    }
+
+    /// Parses a ClassAtom[+UnicodeMode].
+    fn parse_class_atom(&mut self) -> Result<Atom, ClassError> {
+        let start = self.pos;
+        if !self.eat('\\') {
+            return self
+                .parse_source_character()
+                .map(Atom::Character)
+                .ok_or_else(|| self.error_here(ClassErrorKind::MissingCloseBracket));
+        }
+        if let Some(set) = self.parse_class_escape()? {
+            return Ok(Atom::Operand(set));
+        }
+        if self.eat('b') {
+            return Ok(Atom::Character(0x08));
+        }
+        if self.eat('-') {
+            return Ok(Atom::Character('-' as u32));
+        }
+        self.parse_character_escape()
+            .map(Atom::Character)
+            .ok_or_else(|| self.error(ClassErrorKind::InvalidEscape, start))
+    }
+
+    /// Parses the ClassContents of a RegExp with the `u` flag, up to and
+    /// including the `]`.
+    fn parse_class_ranges(&mut self) -> Result<ClassSetExpression, ClassError> {
+        let mut members = vec![];
+        loop {
+            if self.peek().is_none() {
+                return Err(self.error_here(ClassErrorKind::MissingCloseBracket));
+            }
+            if self.eat(']') {
+                return Ok(ClassSetExpression::Union(members));
+            }
+
+            let start = self.pos;
+            let atom = self.parse_class_atom()?;
+            if !self.at('-') || self.peek_at(1).is_none_or(|unit| unit == ']' as u32) {
+                members.push(atom.into_expression());
+                continue;
+            }
+
+            self.pos += 1;
+            let end_offset = self.pos;
+            let range = match (atom, self.parse_class_atom()?) {
+                (Atom::Character(first), Atom::Character(last)) => (first, last),
+                (Atom::Character(_), _) => {
+                    return Err(self.error(ClassErrorKind::InvalidRangeEndpoint, end_offset))
+                }
+                _ => return Err(self.error(ClassErrorKind::InvalidRangeEndpoint, start)),
+            };
+            if range.0 > range.1 {
+                return Err(self.error(ClassErrorKind::RangeOutOfOrder, start));
+            }
+            members.push(ClassSetExpression::Range(range.0, range.1));
+        }
+    }
+
+    /// Parses a ClassSetCharacter.
+    fn parse_class_set_character(&mut self) -> Result<u32, ClassError> {
+        let start = self.pos;
+        let unit = match self.peek() {
+            Some(unit) => unit,
+            None => return Err(self.error_here(ClassErrorKind::MissingCloseBracket)),
+        };
+        if unit == '\\' as u32 {
+            self.pos += 1;
+            if self.eat('b') {
+                return Ok(0x08);
+            }
+            if let Some(punctuator) = self
+                .peek()
+                .filter(|&unit| is_class_set_reserved_punctuator(unit))
+            {
+                self.pos += 1;
+                return Ok(punctuator);
+            }
+            return self
+                .parse_character_escape()
+                .ok_or_else(|| self.error(ClassErrorKind::InvalidEscape, start));
+        }
+        if is_class_set_syntax_character(unit)
+            || (is_double_punctuator_character(unit) && self.peek_at(1) == Some(unit))
+        {
+            return Err(self.error(ClassErrorKind::SyntaxCharacter, start));
+        }
+        Ok(self.parse_source_character().unwrap_or(unit))
+    }
+
+    /// Parses the rest of a ClassStringDisjunction, after the `\q{`.
+    fn parse_class_string_disjunction(&mut self) -> Result<ClassSetExpression, ClassError> {
+        let mut strings = vec![];
+        let mut string = vec![];
+        loop {
+            if self.peek().is_none() {
+                return Err(self.error(ClassErrorKind::MissingCloseBrace, self.pos));
+            }
+            if self.eat('}') {
+                strings.push(string);
+                return Ok(ClassSetExpression::ClassStringDisjunction(strings));
+            }
+            if self.eat('|') {
+                strings.push(std::mem::take(&mut string));
+                continue;
+            }
+            let c = self.parse_class_set_character()?;
+            push_utf16(&mut string, c);
+        }
+    }
+
+    /// Parses a ClassSetOperand or ClassSetCharacter.
+    fn parse_class_set_operand(&mut self) -> Result<Atom, ClassError> {
+        let start = self.pos;
+        if self.eat('[') {
+            return self.parse_nested_class().map(Atom::Operand);
+        }
+        if self.eat('\\') {
+            if let Some(set) = self.parse_class_escape()? {
+                return Ok(Atom::Operand(set));
+            }
+            if self.at('q') && self.peek_at(1) == Some('{' as u32) {
+                self.pos += 2;
+                return self.parse_class_string_disjunction().map(Atom::Operand);
+            }
+            self.pos = start;
+        }
+        self.parse_class_set_character().map(Atom::Character)
+    }
+
+    /// Parses a ClassSetOperand, or a ClassSetRange of a ClassUnion.
+    fn parse_class_union_member(&mut self) -> Result<ClassSetExpression, ClassError> {
+        let start = self.pos;
+        let atom = self.parse_class_set_operand()?;
+        if !self.at('-') || self.at_double('-') {
+            return Ok(atom.into_expression());
+        }
+
+        let first = match atom {
+            Atom::Character(first) => first,
+            Atom::Operand(_) => return Err(self.error(ClassErrorKind::InvalidRangeEndpoint, start)),
+        };
+        self.pos += 1;
+        let end_offset = self.pos;
+        let last = match self.parse_class_set_operand()? {
+            Atom::Character(last) => last,
+            Atom::Operand(_) => {
+                return Err(self.error(ClassErrorKind::InvalidRangeEndpoint, end_offset))
+            }
+        };
+        if first > last {
+            return Err(self.error(ClassErrorKind::RangeOutOfOrder, start));
+        }
+        Ok(ClassSetExpression::Range(first, last))
+    }
+
+    /// Parses a ClassSetExpression, up to and including the `]`.
+    fn parse_class_set_expression(&mut self) -> Result<ClassSetExpression, ClassError> {
+        if self.eat(']') {
+            return Ok(ClassSetExpression::Union(vec![]));
+        }
+
+        let first = self.parse_class_union_member()?;
+        let operator = if self.at_double('&') {
+            '&'
+        } else if self.at_double('-') {
+            '-'
+        } else {
+            // ClassUnion
+            let mut members = vec![first];
+            loop {
+                if self.eat(']') {
+                    return Ok(ClassSetExpression::Union(members));
+                }
+                if self.at_double('&') || self.at_double('-') {
+                    return Err(self.error(ClassErrorKind::InvalidOperation, self.pos));
+                }
+                members.push(self.parse_class_union_member()?);
+            }
+        };
+
+        // ClassIntersection or ClassSubtraction, whose operands cannot be
+        // ranges.
+        if let ClassSetExpression::Range(..) = first {
+            return Err(self.error(ClassErrorKind::InvalidOperation, self.pos));
+        }
+        let mut operands = vec![first];
+        loop {
+            self.pos += 2;
+            if operator == '&' && self.at('&') {
+                return Err(self.error(ClassErrorKind::SyntaxCharacter, self.pos));
+            }
+            operands.push(self.parse_class_set_operand()?.into_expression());
+            if self.eat(']') {
+                break;
+            }
+            if !self.at_double(operator) {
+                return Err(self.error_here(ClassErrorKind::InvalidOperation));
+            }
+        }
+        Ok(if operator == '&' {
+            ClassSetExpression::Intersection(operands)
+        } else {
+            ClassSetExpression::Subtraction(operands)
+        })
+    }
+
+    /// Parses the rest of a class of a RegExp with the `v` flag, after the
+    /// `[`. A complemented class whose contents may contain strings is
+    /// reported at the `^`.
+    fn parse_nested_class(&mut self) -> Result<ClassSetExpression, ClassError> {
+        if !self.at('^') {
+            let contents = self.parse_class_set_expression()?;
+            return Ok(ClassSetExpression::Nested(Box::new(contents)));
+        }
+        let negation = self.pos;
+        self.pos += 1;
+        let contents = self.parse_class_set_expression()?;
+        if contents.may_contain_strings() {
+            return Err(self.error(ClassErrorKind::NegatedStrings, negation));
+        }
+        Ok(ClassSetExpression::Complement(Box::new(contents)))
+    }
+
+    /// Compiles a class of a RegExp with the `u` flag.
+    fn compile_unicode_class(&mut self) -> Result<ICU4XStringSet, ClassError> {
+        let start = self.pos;
+        let negated = self.eat('^');
+        let set = self
+            .parse_class_ranges()?
+            .evaluate(false)
+            .map_err(|e| self.error(e.into(), start))?;
+
+        let mut builder = UnicodeSetBuilder::new();
+        if self.flags.ignore_case {
+            builder.add_set(case_close(set.code_points(), CanonicalizeMode::Unicode).get());
+        } else {
+            builder.add_set(set.code_points().get());
+        }
+        if negated {
+            builder.complement();
+        }
+        Ok(builder.build().into())
+    }
+
+    /// Compiles a class of a RegExp with the `v` flag.
+    fn compile_unicode_sets_class(&mut self) -> Result<ICU4XStringSet, ClassError> {
+        let start = self.pos;
+        let set = self
+            .parse_nested_class()?
+            .evaluate(self.flags.ignore_case)
+            .map_err(|e| self.error(e.into(), start))?;
+        if !self.flags.ignore_case {
+            return Ok(set);
+        }
+        // Close the code points, so that they can be matched without
+        // canonicalizing the input.
+        let code_points = case_close(set.code_points(), CanonicalizeMode::Unicode);
+        Ok(ICU4XStringSet::new(code_points, set.strings().to_vec()))
+    }
+}
+
+/// Compiles the CharacterClass in `pattern`, a Latin1 or UTF-16 buffer,
+/// where `cursor` is the offset just after the `[`. `flags` must include
+/// the `u` or `v` flag.
+///
+/// Returns the set and the number of code units consumed, up to and
+/// including the closing `]`. The offset of an error is relative to the
+/// start of `pattern`.
+///
+/// With the `i` flag, the code points of the set are closed under
+/// [`case_close`], so they can be matched without canonicalizing the
+/// input. With the `v` flag, the strings are simple case folded, and
+/// should be compared with input canonicalized by
+/// [`canonicalize_unicode`](crate::canonicalize_unicode).
+pub fn compile_class<T: Copy + Into<u32>>(
+    pattern: &[T],
+    cursor: usize,
+    flags: RegExpFlags,
+) -> Result<(ICU4XStringSet, usize), ClassError> {
+    let mut parser = Parser {
+        pattern,
+        pos: cursor,
+        flags,
+    };
+    let set = if flags.unicode_sets {
+        parser.compile_unicode_sets_class()?
+    } else if flags.unicode {
+        parser.compile_unicode_class()?
+    } else {
+        return Err(parser.error(ClassErrorKind::UnsupportedFlags, cursor));
+    };
+    Ok((set, parser.pos - cursor))
+}
+
+/// Compiles the class at the start of `pattern`, in UTF-16, for the tests.
+#[cfg(test)]
+fn compile(pattern: &str, flags: RegExpFlags) -> Result<(ICU4XStringSet, usize), ClassError> {
+    let utf16: Vec<u16> = pattern.encode_utf16().collect();
+    compile_class(&utf16, 1, flags)
+}
+
+#[cfg(test)]
+fn contains(pattern: &str, flags: RegExpFlags, c: char) -> bool {
+    compile(pattern, flags)
+        .unwrap()
+        .0
+        .code_points()
+        .get()
+        .contains(c)
+}
+
+#[cfg(test)]
+fn size(pattern: &str, flags: RegExpFlags) -> usize {
+    compile(pattern, flags)
+        .unwrap()
+        .0
+        .code_points()
+        .get()
+        .size()
+}
+
+#[cfg(test)]
+fn strings(pattern: &str, flags: RegExpFlags) -> Vec<String> {
+    compile(pattern, flags)
+        .unwrap()
+        .0
+        .strings()
+        .iter()
+        .map(|s| String::from_utf16(s).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+fn error(pattern: &str, flags: RegExpFlags) -> ClassError {
+    compile(pattern, flags).err().unwrap()
+}
+
+#[cfg(test)]
+fn at(kind: ClassErrorKind, offset: usize) -> ClassError {
+    ClassError { kind, offset }
+}
+
+#[test]
+fn test_compile_class_u() {
+    use ClassErrorKind::*;
+
+    let u = RegExpFlags {
+        unicode: true,
+        ..RegExpFlags::default()
+    };
+    let iu = RegExpFlags {
+        ignore_case: true,
+        ..u
+    };
+
+    assert_eq!(compile("[a-c\\d]+", u).unwrap().1, 6);
+    assert_eq!(size("[a-c\\d]", u), 13);
+    assert_eq!(size("[]", u), 0);
+    assert_eq!(size("[^]", u), 0x110000);
+    assert_eq!(size("[-a-]", u), 2);
+    assert_eq!(size("[a-b-c]", u), 4);
+    assert_eq!(size("[\\u{1F600}-\\u{1F64F}]", u), 0x50);
+    assert_eq!(size("[\\uD83D\\uDE00]", u), 1);
+    assert_eq!(size("[\u{1F600}]", u), 1);
+    assert_eq!(size("[\\uD83D]", u), 1);
+    assert!(contains("[\\cJ\\x41\\0\\b\\-\\/]", u, '\n'));
+    assert_eq!(size("[\\cJ\\x41\\0\\b\\-\\/]", u), 6);
+    assert!(contains("[\\p{Lu}]", u, 'A'));
+    assert!(!contains("[^\\p{Lu}]", u, 'A'));
+
+    // Case closure.
+    assert!(!contains("[k]", u, '\u{212A}'));
+    assert!(contains("[k]", iu, '\u{212A}'));
+    assert!(contains("[k]", iu, 'K'));
+    assert!(!contains("[^k]", iu, 'K'));
+    assert!(contains("[\\P{Lu}]", iu, 'A'));
+
+    assert_eq!(error("[a", u), at(MissingCloseBracket, 2));
+    assert_eq!(error("[a-", u), at(MissingCloseBracket, 3));
+    assert_eq!(error("[z-a]", u), at(RangeOutOfOrder, 1));
+    assert_eq!(error("[a\\w-z]", u), at(InvalidRangeEndpoint, 2));
+    assert_eq!(error("[a-\\w]", u), at(InvalidRangeEndpoint, 3));
+    assert_eq!(error("[\\k]", u), at(InvalidEscape, 1));
+    assert_eq!(error("[\\x4]", u), at(InvalidEscape, 1));
+    assert_eq!(error("[\\01]", u), at(InvalidEscape, 1));
+    assert_eq!(error("[\\c1]", u), at(InvalidEscape, 1));
+    assert_eq!(error("[\\u{110000}]", u), at(InvalidEscape, 1));
+    assert_eq!(error("[\\q{ab}]", u), at(InvalidEscape, 1));
+    assert_eq!(error("[\\p{Foo}]", u), at(UnknownName, 4));
+    assert_eq!(error("[\\p{RGI_Emoji}]", u), at(UnknownName, 4));
+    assert_eq!(
+        error("[a]", RegExpFlags::default()),
+        at(UnsupportedFlags, 1)
+    );
+
+    // Latin1 patterns compile the same way.
+    let (set, length) = compile_class(b"[^\xE0-\xFF]", 1, u).unwrap();
+    assert_eq!(length, 5);
+    assert_eq!(set.code_points().get().size(), 0x110000 - 0x20);
+}
+
+#[test]
+fn test_compile_class_v() {
+    use ClassErrorKind::*;
+
+    let v = RegExpFlags {
+        unicode_sets: true,
+        ..RegExpFlags::default()
+    };
+    let iv = RegExpFlags {
+        ignore_case: true,
+        ..v
+    };
+
+    assert_eq!(compile("[[a-z]&&[aeiou]]x", v).unwrap().1, 15);
+    assert_eq!(size("[[a-z]&&[aeiou]]", v), 5);
+    assert_eq!(size("[[a-z]--[aeiou]--x]", v), 20);
+    assert_eq!(size("[\\d[a-c]\\q{x|y}]", v), 15);
+    assert_eq!(size("[^[^a]]", v), 1);
+    assert_eq!(size("[\\!\\&\\-]", v), 3);
+    assert_eq!(size("[&]", v), 1);
+    assert_eq!(
+        strings("[\\q{ab|c|}\\q{\\u{1F600}\\x41}]", v),
+        ["", "ab", "\u{1F600}A"]
+    );
+    assert_eq!(
+        strings(
+            "[\\p{RGI_Emoji_Flag_Sequence}&&\\q{\u{1F1EB}\u{1F1F7}|\u{1F1FD}\u{1F1FD}}]",
+            v
+        ),
+        ["\u{1F1EB}\u{1F1F7}"]
+    );
+
+    // Case folding.
+    assert!(contains("[\\p{Lu}&&[a-z]]", iv, 'k'));
+    assert!(contains("[\\p{Lu}&&[a-z]]", iv, '\u{212A}'));
+    assert_eq!(size("[\\p{Lu}&&[a-z]]", v), 0);
+    assert!(!contains("[\\P{Lu}]", iv, 'a'));
+    assert!(!contains("[\\P{Lu}]", iv, 'A'));
+    assert!(!contains("[^k]", iv, '\u{212A}'));
+    assert_eq!(strings("[\\q{KA}]", iv), ["ka"]);
+
+    assert_eq!(error("[a", v), at(MissingCloseBracket, 2));
+    assert_eq!(error("[a-]", v), at(SyntaxCharacter, 3));
+    assert_eq!(error("[(]", v), at(SyntaxCharacter, 1));
+    assert_eq!(error("[a!!]", v), at(SyntaxCharacter, 2));
+    assert_eq!(error("[a&&&b]", v), at(SyntaxCharacter, 4));
+    assert_eq!(error("[a&&b--c]", v), at(InvalidOperation, 5));
+    assert_eq!(error("[ab&&c]", v), at(InvalidOperation, 3));
+    assert_eq!(error("[a-z&&b]", v), at(InvalidOperation, 4));
+    assert_eq!(error("[a&&b-c]", v), at(InvalidOperation, 5));
+    assert_eq!(error("[z-a]", v), at(RangeOutOfOrder, 1));
+    assert_eq!(error("[\\d-z]", v), at(InvalidRangeEndpoint, 1));
+    assert_eq!(error("[a-[b]]", v), at(InvalidRangeEndpoint, 3));
+    assert_eq!(error("[\\q{a]", v), at(SyntaxCharacter, 5));
+    assert_eq!(error("[\\q{a", v), at(MissingCloseBrace, 5));
+    assert_eq!(error("[^\\q{ab}]", v), at(NegatedStrings, 1));
+    assert_eq!(error("[^[\\q{ab}--\\q{ab}]]", v), at(NegatedStrings, 1));
+    assert_eq!(error("[^\\q{a|}]", v), at(NegatedStrings, 1));
+    assert_eq!(size("[^\\q{a}]", v), 0x110000 - 1);
+    assert_eq!(size("[^[\\p{RGI_Emoji}&&\\u{1F600}]]", v), 0x110000 - 1);
+    assert_eq!(error("[a[^\\p{RGI_Emoji}]]", v), at(NegatedStrings, 3));
+    assert_eq!(error("[\\P{RGI_Emoji}]", v), at(NegatedStrings, 4));
+}
@@ -0,0 +1,191 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! The sets matched by the
+//! [CharacterClassEscape](https://tc39.es/ecma262/multipage/text-processing.html#sec-compiletocharset)
+//! productions and by `.`.
+
+use crate::property::{get_code_point_set, Property};
+use crate::{case_close, CanonicalizeMode, GeneralCategory, ICU4XUniset, PropertyError};
+use icu_uniset::UnicodeSetBuilder;
+
+/// The flags of a RegExp that affect the characters it matches.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RegExpFlags {
+    /// The `u` flag.
+    pub unicode: bool,
+    /// The `v` flag.
+    pub unicode_sets: bool,
+    /// The `i` flag.
+    pub ignore_case: bool,
+    /// The `s` flag.
+    pub dot_all: bool,
+}
+
+impl RegExpFlags {
+    /// Whether characters are code points rather than code units.
+    pub fn has_either_unicode_flag(&self) -> bool {
+        self.unicode || self.unicode_sets
+    }
+
+    /// The Canonicalize used when `ignore_case` is set.
+    pub fn canonicalize_mode(&self) -> CanonicalizeMode {
+        if self.has_either_unicode_flag() {
+            CanonicalizeMode::Unicode
+        } else {
+            CanonicalizeMode::Legacy
+        }
+    }
+}
+
+/// A CharacterClassEscape, or `.`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassEscape {
+    /// `\d`
+    Digit = 0,
+    /// `\D`
+    NotDigit = 1,
+    /// `\s`: WhiteSpace and LineTerminator.
+    Space = 2,
+    /// `\S`
+    NotSpace = 3,
+    /// `\w`: WordCharacters.
+    Word = 4,
+    /// `\W`
+    NotWord = 5,
+    /// `.`
+    Dot = 6,
+}
+
+const LINE_TERMINATORS: &[char] = &['\n', '\r', '\u{2028}', '\u{2029}'];
+
+/// Returns the set of characters matched by `escape` in a RegExp with
+/// `flags`. Without the `u` or `v` flag, characters are code units, so
+/// the complemented sets stop at U+FFFF.
+pub fn class_escape_set(
+    escape: ClassEscape,
+    flags: RegExpFlags,
+) -> Result<ICU4XUniset, PropertyError> {
+    let mut builder = UnicodeSetBuilder::new();
+    match escape {
+        ClassEscape::Digit | ClassEscape::NotDigit => builder.add_range(&('0'..='9')),
+        ClassEscape::Space | ClassEscape::NotSpace => {
+            // WhiteSpace is Zs plus the characters below.
+            let zs =
+                get_code_point_set(Property::GeneralCategory(GeneralCategory::SpaceSeparator))?;
+            builder.add_set(zs.get());
+            for &c in ['\t', '\u{B}', '\u{C}', ' ', '\u{A0}', '\u{FEFF}'].iter() {
+                builder.add_char(c);
+            }
+            for &c in LINE_TERMINATORS {
+                builder.add_char(c);
+            }
+        }
+        ClassEscape::Word | ClassEscape::NotWord => {
+            builder.add_range(&('a'..='z'));
+            builder.add_range(&('A'..='Z'));
+            builder.add_range(&('0'..='9'));
+            builder.add_char('_');
+            // The extraWordChars of WordCharacters: with the `u` or `v`
+            // flag, the characters that canonicalize into the basic word
+            // characters under `i`, namely U+017F and U+212A.
+            if flags.ignore_case && flags.has_either_unicode_flag() {
+                let basic: ICU4XUniset = builder.build().into();
+                builder = UnicodeSetBuilder::new();
+                builder.add_set(case_close(&basic, flags.canonicalize_mode()).get());
+            }
+        }
+        ClassEscape::Dot => {
+            if !flags.dot_all {
+                for &c in LINE_TERMINATORS {
+                    builder.add_char(c);
+                }
+            }
+        }
+    }
+
+    if let ClassEscape::NotDigit | ClassEscape::NotSpace | ClassEscape::NotWord | ClassEscape::Dot =
+        escape
+    {
+        builder.complement();
+        if !flags.has_either_unicode_flag() {
+            builder.remove_range(&('\u{10000}'..='\u{10FFFF}'));
+        }
+    }
+    Ok(builder.build().into())
+}
+
+#[test]
+fn test_class_escape_set() {
+    let legacy = RegExpFlags::default();
+    let unicode = RegExpFlags {
+        unicode: true,
+        ..legacy
+    };
+    let contains = |escape, flags, c| class_escape_set(escape, flags).unwrap().get().contains(c);
+
+    assert!(contains(ClassEscape::Digit, legacy, '7'));
+    assert!(!contains(ClassEscape::Digit, unicode, '\u{0661}')); // ARABIC-INDIC DIGIT ONE
+    assert!(contains(ClassEscape::NotDigit, unicode, '\u{0661}'));
+
+    // \s differs from White_Space in U+FEFF and U+0085.
+    for &flags in [legacy, unicode].iter() {
+        assert!(contains(ClassEscape::Space, flags, '\u{FEFF}'));
+        assert!(contains(ClassEscape::Space, flags, '\u{3000}'));
+        assert!(contains(ClassEscape::Space, flags, '\u{2029}'));
+        assert!(!contains(ClassEscape::Space, flags, '\u{85}'));
+        assert!(!contains(ClassEscape::Space, flags, '\u{180E}'));
+        assert!(contains(ClassEscape::NotSpace, flags, '\u{85}'));
+    }
+
+    // The extra word characters are only added with `iu` or `iv`.
+    let word_set = |flags| class_escape_set(ClassEscape::Word, flags).unwrap();
+    assert_eq!(word_set(legacy).get().size(), 63);
+    assert_eq!(word_set(unicode).get().size(), 63);
+    let ignore_case = RegExpFlags {
+        ignore_case: true,
+        ..legacy
+    };
+    assert_eq!(word_set(ignore_case).get().size(), 63);
+    for &flags in [
+        RegExpFlags {
+            unicode: true,
+            ..ignore_case
+        },
+        RegExpFlags {
+            unicode_sets: true,
+            ..ignore_case
+        },
+    ]
+    .iter()
+    {
+        assert_eq!(word_set(flags).get().size(), 65);
+        assert!(contains(ClassEscape::Word, flags, '\u{017F}'));
+        assert!(contains(ClassEscape::Word, flags, '\u{212A}'));
+        assert!(!contains(ClassEscape::NotWord, flags, '\u{212A}'));
+    }
+
+    // Without `u` or `v`, characters are code units.
+    assert!(!contains(ClassEscape::NotWord, legacy, '\u{10000}'));
+    assert!(contains(ClassEscape::NotWord, unicode, '\u{10000}'));
+    assert!(contains(ClassEscape::Dot, legacy, '\u{FFFF}'));
+    assert!(!contains(ClassEscape::Dot, legacy, '\u{10000}'));
+    assert!(contains(ClassEscape::Dot, unicode, '\u{10FFFF}'));
+
+    assert!(!contains(ClassEscape::Dot, unicode, '\n'));
+    assert!(!contains(ClassEscape::Dot, unicode, '\u{2028}'));
+    let dot_all = RegExpFlags {
+        dot_all: true,
+        ..unicode
+    };
+    assert!(contains(ClassEscape::Dot, dot_all, '\n'));
+    assert_eq!(
+        class_escape_set(ClassEscape::Dot, dot_all)
+            .unwrap()
+            .get()
+            .size(),
+        0x110000
+    );
+}
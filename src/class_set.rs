@@ -0,0 +1,480 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! Evaluation of the
+//! [ClassSetExpression](https://tc39.es/ecma262/multipage/text-processing.html#prod-ClassSetExpression)
+//! of a RegExp with the `v` flag.
+
+use crate::canonicalize::{canonicalize_unicode, simple_case_fold, simple_case_folded_characters};
+use crate::{ClassSetError, ICU4XStringSet, ICU4XUniset};
+use icu_uniset::UnicodeSetBuilder;
+
+/// A parsed ClassSetExpression. Property escapes and class escapes are
+/// resolved by the parser, and appear as [`ClassSetExpression::Set`].
+pub enum ClassSetExpression {
+    /// A ClassSetCharacter.
+    Character(u32),
+    /// A ClassSetRange, from the first code point to the second.
+    Range(u32, u32),
+    /// A `\q{…}` ClassStringDisjunction: its ClassStrings, in UTF-16.
+    ClassStringDisjunction(Vec<Vec<u16>>),
+    /// A resolved set, such as a property escape.
+    Set(ICU4XStringSet),
+    /// A ClassUnion.
+    Union(Vec<ClassSetExpression>),
+    /// A ClassIntersection: `A&&B&&…`.
+    Intersection(Vec<ClassSetExpression>),
+    /// A ClassSubtraction: `A--B--…`.
+    Subtraction(Vec<ClassSetExpression>),
+    /// A NestedClass that is not complemented: `[…]`.
+    Nested(Box<ClassSetExpression>),
+    /// A NestedClass that is complemented: `[^…]`.
+    Complement(Box<ClassSetExpression>),
+}
+
+impl ClassSetExpression {
+    /// The
+    /// [MayContainStrings](https://tc39.es/ecma262/multipage/text-processing.html#sec-static-semantics-maycontainstrings)
+    /// static semantics, which depend on the syntax of the class rather than
+    /// on its value: `[\q{ab}--\q{ab}]` may contain strings even though it
+    /// is empty. A resolved set may contain strings if it has any, which
+    /// for the sets of this crate is whether it is a property of strings.
+    pub fn may_contain_strings(&self) -> bool {
+        match self {
+            ClassSetExpression::Character(_)
+            | ClassSetExpression::Range(..)
+            | ClassSetExpression::Complement(_) => false,
+            ClassSetExpression::ClassStringDisjunction(strings) => strings
+                .iter()
+                .any(|string| std::char::decode_utf16(string.iter().copied()).count() != 1),
+            ClassSetExpression::Set(set) => set.has_strings(),
+            ClassSetExpression::Union(operands) => {
+                operands.iter().any(ClassSetExpression::may_contain_strings)
+            }
+            ClassSetExpression::Intersection(operands) => {
+                !operands.is_empty() && operands.iter().all(ClassSetExpression::may_contain_strings)
+            }
+            ClassSetExpression::Subtraction(operands) => operands
+                .first()
+                .is_some_and(ClassSetExpression::may_contain_strings),
+            ClassSetExpression::Nested(operand) => operand.may_contain_strings(),
+        }
+    }
+
+    /// Checks the early errors of the expression and its operands:
+    ///
+    /// - [`ClassSetError::NegatedStrings`] for a complement that may
+    ///   contain strings, such as `[^\q{ab}]` or `\P{RGI_Emoji}`.
+    /// - [`ClassSetError::MixedOperators`] for an operand of an
+    ///   intersection or subtraction that is a range, a union or another
+    ///   operation, unless it is in a [`ClassSetExpression::Nested`] class:
+    ///   `[a&&b--c]`, `[ab&&c]`, `[\q{a}\q{b}&&c]` or `[a-z&&b]`. A
+    ///   [`ClassSetExpression::ClassStringDisjunction`] like `\q{a|b}` is a
+    ///   single operand.
+    /// - [`ClassSetError::RangeOutOfOrder`] for a range like `z-a`.
+    pub fn validate(&self) -> Result<(), ClassSetError> {
+        match self {
+            ClassSetExpression::Range(start, end) if start > end => {
+                Err(ClassSetError::RangeOutOfOrder)
+            }
+            ClassSetExpression::Character(_)
+            | ClassSetExpression::Range(..)
+            | ClassSetExpression::ClassStringDisjunction(_)
+            | ClassSetExpression::Set(_) => Ok(()),
+            ClassSetExpression::Union(operands) => {
+                operands.iter().try_for_each(ClassSetExpression::validate)
+            }
+            ClassSetExpression::Intersection(operands)
+            | ClassSetExpression::Subtraction(operands) => {
+                if !operands.iter().all(ClassSetExpression::is_operand) {
+                    return Err(ClassSetError::MixedOperators);
+                }
+                operands.iter().try_for_each(ClassSetExpression::validate)
+            }
+            ClassSetExpression::Nested(operand) => operand.validate(),
+            ClassSetExpression::Complement(operand) => {
+                if operand.may_contain_strings() {
+                    return Err(ClassSetError::NegatedStrings);
+                }
+                operand.validate()
+            }
+        }
+    }
+
+    /// Whether the expression is a ClassSetOperand, which can be an
+    /// operand of an intersection or subtraction without nesting.
+    fn is_operand(&self) -> bool {
+        !matches!(
+            self,
+            ClassSetExpression::Range(..)
+                | ClassSetExpression::Union(_)
+                | ClassSetExpression::Intersection(_)
+                | ClassSetExpression::Subtraction(_)
+        )
+    }
+
+    /// Evaluates the expression to a set of code points and strings.
+    ///
+    /// With `ignore_case`, the operands are simple case folded, and
+    /// complements are taken in the set of simple case folded code points,
+    /// so the result should be matched against canonicalized input.
+    ///
+    /// Complements are checked with [`Self::may_contain_strings`], but
+    /// mixed operators are not; use [`Self::validate`] for those.
+    pub fn evaluate(&self, ignore_case: bool) -> Result<ICU4XStringSet, ClassSetError> {
+        let mut builder = UnicodeSetBuilder::new();
+        let mut strings = vec![];
+        match self {
+            ClassSetExpression::Character(code_point) => {
+                builder.add_range_u32(&(*code_point..=*code_point));
+            }
+            ClassSetExpression::Range(start, end) => {
+                if start > end {
+                    return Err(ClassSetError::RangeOutOfOrder);
+                }
+                builder.add_range_u32(&(*start..=*end));
+            }
+            ClassSetExpression::ClassStringDisjunction(class_strings) => {
+                for string in class_strings {
+                    let mut code_points = std::char::decode_utf16(string.iter().copied());
+                    match (code_points.next(), code_points.next()) {
+                        (Some(Ok(c)), None) => builder.add_char(c),
+                        (Some(Err(e)), None) => {
+                            let surrogate = e.unpaired_surrogate() as u32;
+                            builder.add_range_u32(&(surrogate..=surrogate))
+                        }
+                        _ => strings.push(string.clone()),
+                    }
+                }
+            }
+            ClassSetExpression::Set(set) => {
+                builder.add_set(set.code_points().get());
+                strings.extend_from_slice(set.strings());
+            }
+            ClassSetExpression::Union(operands) => {
+                return evaluate_operands(operands, ignore_case, union);
+            }
+            ClassSetExpression::Intersection(operands) => {
+                return evaluate_operands(operands, ignore_case, intersection);
+            }
+            ClassSetExpression::Subtraction(operands) => {
+                return evaluate_operands(operands, ignore_case, subtraction);
+            }
+            ClassSetExpression::Nested(operand) => return operand.evaluate(ignore_case),
+            ClassSetExpression::Complement(operand) => {
+                if operand.may_contain_strings() {
+                    return Err(ClassSetError::NegatedStrings);
+                }
+                let set = operand.evaluate(ignore_case)?;
+                if ignore_case {
+                    builder.add_set(simple_case_folded_characters().get());
+                    builder.remove_set(set.code_points().get());
+                } else {
+                    builder.add_set(set.code_points().get());
+                    builder.complement();
+                }
+                return Ok(builder.build().into());
+            }
+        }
+
+        let code_points: ICU4XUniset = builder.build().into();
+        if ignore_case {
+            let strings = strings.iter().map(|string| fold_string(string)).collect();
+            Ok(ICU4XStringSet::new(simple_case_fold(&code_points), strings))
+        } else {
+            Ok(ICU4XStringSet::new(code_points, strings))
+        }
+    }
+}
+
+/// Maps each code point of a UTF-16 string with [`canonicalize_unicode`].
+fn fold_string(string: &[u16]) -> Vec<u16> {
+    let mut folded = vec![];
+    for c in std::char::decode_utf16(string.iter().copied()) {
+        match c {
+            Ok(c) => {
+                let c = std::char::from_u32(canonicalize_unicode(c as u32)).unwrap_or(c);
+                folded.extend_from_slice(c.encode_utf16(&mut [0; 2]));
+            }
+            Err(e) => folded.push(e.unpaired_surrogate()),
+        }
+    }
+    folded
+}
+
+/// Evaluates the operands of a ClassUnion, ClassIntersection or
+/// ClassSubtraction, and combines them from left to right.
+fn evaluate_operands(
+    operands: &[ClassSetExpression],
+    ignore_case: bool,
+    combine: fn(&ICU4XStringSet, &ICU4XStringSet) -> ICU4XStringSet,
+) -> Result<ICU4XStringSet, ClassSetError> {
+    let mut operands = operands.iter().map(|operand| operand.evaluate(ignore_case));
+    let first = match operands.next() {
+        Some(first) => first?,
+        None => {
+            return Ok(ICU4XStringSet::new(
+                UnicodeSetBuilder::new().build().into(),
+                vec![],
+            ))
+        }
+    };
+    operands.try_fold(first, |result, operand| Ok(combine(&result, &operand?)))
+}
+
+fn union(a: &ICU4XStringSet, b: &ICU4XStringSet) -> ICU4XStringSet {
+    let mut builder = UnicodeSetBuilder::new();
+    builder.add_set(a.code_points().get());
+    builder.add_set(b.code_points().get());
+    let strings = a.strings().iter().chain(b.strings()).cloned().collect();
+    ICU4XStringSet::new(builder.build().into(), strings)
+}
+
+fn intersection(a: &ICU4XStringSet, b: &ICU4XStringSet) -> ICU4XStringSet {
+    let mut builder = UnicodeSetBuilder::new();
+    builder.add_set(a.code_points().get());
+    builder.retain_set(b.code_points().get());
+    let strings = a
+        .strings()
+        .iter()
+        .filter(|&string| b.strings().binary_search(string).is_ok())
+        .cloned()
+        .collect();
+    ICU4XStringSet::new(builder.build().into(), strings)
+}
+
+fn subtraction(a: &ICU4XStringSet, b: &ICU4XStringSet) -> ICU4XStringSet {
+    let mut builder = UnicodeSetBuilder::new();
+    builder.add_set(a.code_points().get());
+    builder.remove_set(b.code_points().get());
+    let strings = a
+        .strings()
+        .iter()
+        .filter(|&string| b.strings().binary_search(string).is_err())
+        .cloned()
+        .collect();
+    ICU4XStringSet::new(builder.build().into(), strings)
+}
+
+/// The set of a property, for the tests.
+#[cfg(test)]
+fn property(name: &str) -> ClassSetExpression {
+    use crate::{try_get_string_set_with_mode, MatchingMode};
+    ClassSetExpression::Set(
+        try_get_string_set_with_mode(name, None, MatchingMode::Ecma262).unwrap(),
+    )
+}
+
+/// A ClassStringDisjunction, for the tests.
+#[cfg(test)]
+fn disjunction(strings: &[&str]) -> ClassSetExpression {
+    ClassSetExpression::ClassStringDisjunction(
+        strings.iter().map(|s| s.encode_utf16().collect()).collect(),
+    )
+}
+
+/// A ClassStringDisjunction of one ClassString, for the tests.
+#[cfg(test)]
+fn string(s: &str) -> ClassSetExpression {
+    disjunction(&[s])
+}
+
+#[test]
+fn test_class_set_expression() {
+    use ClassSetExpression::*;
+
+    let strings = |set: &ICU4XStringSet| -> Vec<String> {
+        set.strings()
+            .iter()
+            .map(|s| String::from_utf16(s).unwrap())
+            .collect()
+    };
+
+    // [a-c\q{xy|z|}]
+    let set = Union(vec![
+        Range('a' as u32, 'c' as u32),
+        disjunction(&["xy", "z", ""]),
+    ])
+    .evaluate(false)
+    .unwrap();
+    assert_eq!(set.code_points().get().size(), 4);
+    assert!(set.code_points().get().contains('z'));
+    assert_eq!(strings(&set), ["", "xy"]);
+
+    // [\p{RGI_Emoji_Flag_Sequence}&&[\q{🇫🇷|🇽🇽}]]
+    let set = Intersection(vec![
+        property("RGI_Emoji_Flag_Sequence"),
+        disjunction(&["\u{1F1EB}\u{1F1F7}", "\u{1F1FD}\u{1F1FD}"]),
+    ])
+    .evaluate(false)
+    .unwrap();
+    assert_eq!(strings(&set), ["\u{1F1EB}\u{1F1F7}"]);
+
+    // [\p{ASCII_Hex_Digit}--[a-f]--\q{0}]
+    let set = Subtraction(vec![
+        property("ASCII_Hex_Digit"),
+        Range('a' as u32, 'f' as u32),
+        string("0"),
+    ])
+    .evaluate(false)
+    .unwrap();
+    assert_eq!(set.code_points().get().size(), 15);
+    assert!(!set.code_points().get().contains('0'));
+
+    // [^[^a]] is [a], and characters may be lone surrogates.
+    let set = Complement(Box::new(Complement(Box::new(Union(vec![
+        Character('a' as u32),
+        Character(0xD800),
+    ])))))
+    .evaluate(false)
+    .unwrap();
+    assert_eq!(set.code_points().get().size(), 2);
+    assert!(set.code_points().get().contains('a'));
+
+    assert_eq!(
+        Range('z' as u32, 'a' as u32).evaluate(false).err(),
+        Some(ClassSetError::RangeOutOfOrder)
+    );
+    assert_eq!(
+        Complement(Box::new(string("ab"))).evaluate(false).err(),
+        Some(ClassSetError::NegatedStrings)
+    );
+}
+
+#[test]
+fn test_class_set_expression_ignore_case() {
+    use ClassSetExpression::*;
+
+    // [\p{Lu}&&[a-z]] is only non-empty with `i`.
+    let expression = Intersection(vec![property("Lu"), Range('a' as u32, 'z' as u32)]);
+    assert_eq!(
+        expression
+            .evaluate(false)
+            .unwrap()
+            .code_points()
+            .get()
+            .size(),
+        0
+    );
+    let set = expression.evaluate(true).unwrap();
+    assert_eq!(set.code_points().get().size(), 26);
+    assert!(set.code_points().get().contains('k'));
+    assert!(!set.code_points().get().contains('K'));
+
+    // [^a] contains neither `a` nor `A`, which canonicalizes to `a`.
+    let set = Complement(Box::new(Character('a' as u32)))
+        .evaluate(true)
+        .unwrap();
+    assert!(!set.code_points().get().contains('a'));
+    assert!(!set.code_points().get().contains('A'));
+    assert!(set.code_points().get().contains('b'));
+
+    // Strings are folded too.
+    let set = string("\u{212A}A").evaluate(true).unwrap();
+    assert_eq!(set.strings(), [vec!['k' as u16, 'a' as u16]]);
+}
+
+#[test]
+fn test_may_contain_strings() {
+    use ClassSetExpression::*;
+
+    let complement = |operand| Complement(Box::new(operand));
+
+    assert!(!Character('a' as u32).may_contain_strings());
+    assert!(!string("a").may_contain_strings());
+    assert!(!string("\u{1F600}").may_contain_strings());
+    assert!(string("").may_contain_strings());
+    assert!(string("ab").may_contain_strings());
+    assert!(property("RGI_Emoji").may_contain_strings());
+    assert!(!property("Emoji").may_contain_strings());
+    assert!(!complement(Character('a' as u32)).may_contain_strings());
+
+    // \q{a|bc}, [\q{a}\q{bc}] and [\p{RGI_Emoji}&&\q{a}]
+    assert!(disjunction(&["a", "bc"]).may_contain_strings());
+    assert!(Union(vec![string("a"), string("bc")]).may_contain_strings());
+    assert!(!Intersection(vec![property("RGI_Emoji"), string("a")]).may_contain_strings());
+    assert!(Intersection(vec![property("RGI_Emoji"), string("ab")]).may_contain_strings());
+
+    // [\q{ab}--\q{ab}] is empty, but may contain strings, so its
+    // complement is an error. [a--\q{ab}] may not.
+    let subtraction = Subtraction(vec![string("ab"), string("ab")]);
+    assert!(subtraction.may_contain_strings());
+    assert_eq!(subtraction.evaluate(false).unwrap().strings().len(), 0);
+    assert_eq!(
+        complement(Nested(Box::new(subtraction))).validate(),
+        Err(ClassSetError::NegatedStrings)
+    );
+    let subtraction = Subtraction(vec![Character('a' as u32), string("ab")]);
+    assert!(!subtraction.may_contain_strings());
+    assert_eq!(complement(subtraction).validate(), Ok(()));
+
+    // \P{RGI_Emoji} and [^\q{ab}], also in an operand.
+    assert_eq!(
+        complement(property("RGI_Emoji")).validate(),
+        Err(ClassSetError::NegatedStrings)
+    );
+    assert_eq!(
+        Union(vec![Character('a' as u32), complement(string("ab"))]).validate(),
+        Err(ClassSetError::NegatedStrings)
+    );
+    assert_eq!(
+        complement(Union(vec![string("a"), string("bc")]))
+            .evaluate(false)
+            .err(),
+        Some(ClassSetError::NegatedStrings)
+    );
+
+    // [a&&b--c] mixes operators, but [a&&[b--c]] does not.
+    let mixed = || {
+        Intersection(vec![
+            Character('a' as u32),
+            Subtraction(vec![Character('b' as u32), Character('c' as u32)]),
+        ])
+    };
+    assert_eq!(mixed().validate(), Err(ClassSetError::MixedOperators));
+    assert_eq!(
+        Intersection(vec![Character('a' as u32), Nested(Box::new(mixed()))]).validate(),
+        Err(ClassSetError::MixedOperators)
+    );
+    assert_eq!(
+        Intersection(vec![
+            Character('a' as u32),
+            Nested(Box::new(Subtraction(vec![
+                Character('b' as u32),
+                Character('c' as u32),
+            ]))),
+        ])
+        .validate(),
+        Ok(())
+    );
+
+    // [ab&&c], [\q{a}\q{b}&&c] and [a-z&&b] need nested classes too, but
+    // \q{a|b} does not.
+    let intersection = |first| Intersection(vec![first, Character('c' as u32)]);
+    let union = || Union(vec![Character('a' as u32), Character('b' as u32)]);
+    let range = || Range('a' as u32, 'z' as u32);
+    assert_eq!(
+        intersection(union()).validate(),
+        Err(ClassSetError::MixedOperators)
+    );
+    assert_eq!(
+        intersection(range()).validate(),
+        Err(ClassSetError::MixedOperators)
+    );
+    assert_eq!(
+        Subtraction(vec![union(), Character('c' as u32)]).validate(),
+        Err(ClassSetError::MixedOperators)
+    );
+    assert_eq!(intersection(Nested(Box::new(union()))).validate(), Ok(()));
+    assert_eq!(intersection(Nested(Box::new(range()))).validate(), Ok(()));
+    assert_eq!(
+        intersection(Union(vec![string("a"), string("b")])).validate(),
+        Err(ClassSetError::MixedOperators)
+    );
+    assert_eq!(intersection(disjunction(&["a", "b"])).validate(), Ok(()));
+
+    assert_eq!(
+        Range('z' as u32, 'a' as u32).validate(),
+        Err(ClassSetError::RangeOutOfOrder)
+    );
+}
@@ -0,0 +1,124 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! The General_Category and Script values of individual code points.
+
+use crate::tables::{GENERAL_CATEGORIES, SCRIPTS};
+use icu_uniset::enum_props::{GeneralCategory, Script};
+
+/// A lookup table from code points to values. See build.rs for its
+/// layout.
+pub(crate) struct CodePointTrie<T: 'static> {
+    index1: &'static [u16],
+    index2: &'static [u16],
+    leaves: &'static [T],
+    /// The value of integers beyond U+10FFFF.
+    default: T,
+}
+
+// Generated by build.rs from data/gc.toml and data/sc.toml:
+// TRIE_INDEX2_BLOCK and TRIE_LEAF_BLOCK, the block sizes, which are
+// powers of two, and GENERAL_CATEGORY_TRIE and SCRIPT_TRIE.
+include!(concat!(env!("OUT_DIR"), "/code_point_tries.rs"));
+
+const LEAF_BITS: u32 = TRIE_LEAF_BLOCK.trailing_zeros();
+const INDEX2_BITS: u32 = TRIE_INDEX2_BLOCK.trailing_zeros();
+
+impl<T: Copy> CodePointTrie<T> {
+    fn get(&self, code_point: u32) -> T {
+        if code_point > 0x10FFFF {
+            return self.default;
+        }
+        let code_point = code_point as usize;
+        let index2 = self.index1[code_point >> (LEAF_BITS + INDEX2_BITS)] as usize;
+        let leaf = self.index2
+            [index2 * TRIE_INDEX2_BLOCK + ((code_point >> LEAF_BITS) & (TRIE_INDEX2_BLOCK - 1))]
+            as usize;
+        self.leaves[leaf * TRIE_LEAF_BLOCK + (code_point & (TRIE_LEAF_BLOCK - 1))]
+    }
+}
+
+/// Returns the General_Category value of a code point. Surrogates are
+/// `Surrogate`, and integers beyond U+10FFFF are `Unassigned`.
+pub fn general_category_of(code_point: u32) -> GeneralCategory {
+    GENERAL_CATEGORY_TRIE.get(code_point)
+}
+
+/// Returns the Script value of a code point. Integers beyond U+10FFFF
+/// are `Unknown` (`Zzzz`).
+pub fn script_of(code_point: u32) -> Script {
+    SCRIPT_TRIE.get(code_point)
+}
+
+/// Returns the long and short names of a General_Category value, like
+/// `("Uppercase_Letter", "Lu")`.
+pub fn general_category_names(gc: GeneralCategory) -> Option<(&'static str, &'static str)> {
+    GENERAL_CATEGORIES
+        .iter()
+        .find(|&&(_, _, category)| category == gc)
+        .map(|&(key, names, _)| (names[0], short_name(key)))
+}
+
+/// Returns the long and short names of a Script value, like
+/// `("Greek", "Grek")`.
+pub fn script_names(script: Script) -> Option<(&'static str, &'static str)> {
+    SCRIPTS
+        .iter()
+        .find(|&&(_, _, s)| s == script)
+        .map(|&(key, names, _)| (names[0], short_name(key)))
+}
+
+/// Returns the short name of a value from its data key, like `gc=Lu`.
+fn short_name(key: &'static str) -> &'static str {
+    &key[key.find('=').map_or(0, |i| i + 1)..]
+}
+
+#[test]
+fn test_general_category_of() {
+    assert_eq!(
+        general_category_of('A' as u32),
+        GeneralCategory::UppercaseLetter
+    );
+    assert_eq!(general_category_of('1' as u32), GeneralCategory::Digit);
+    assert_eq!(general_category_of(0x378), GeneralCategory::Unassigned);
+    assert_eq!(general_category_of(0xD800), GeneralCategory::Surrogate);
+    assert_eq!(general_category_of(0x110000), GeneralCategory::Unassigned);
+    assert_eq!(
+        general_category_names(GeneralCategory::Digit),
+        Some(("Decimal_Number", "Nd"))
+    );
+
+    // Every code point should be in the set of its General_Category.
+    let sets: Vec<_> = GENERAL_CATEGORIES
+        .iter()
+        .map(|&(_, names, gc)| (gc, crate::get_unicode_set("gc", Some(names[0])).unwrap()))
+        .collect();
+    for c in (0..=0x10FFFF).filter_map(std::char::from_u32) {
+        let gc = general_category_of(c as u32);
+        let (_, set) = sets.iter().find(|(category, _)| *category == gc).unwrap();
+        assert!(set.get().contains(c), "U+{:04X}", c as u32);
+    }
+}
+
+#[test]
+fn test_script_of() {
+    let greek = script_of(0x3B1); // U+03B1 GREEK SMALL LETTER ALPHA
+    assert_eq!(script_names(greek), Some(("Greek", "Grek")));
+    assert_eq!(script_names(script_of(0x110000)), Some(("Unknown", "Zzzz")));
+
+    // Every code point should be in the set of its Script.
+    let sets: Vec<_> = SCRIPTS
+        .iter()
+        .map(|&(_, names, script)| {
+            (
+                script,
+                crate::get_unicode_set("sc", Some(names[0])).unwrap(),
+            )
+        })
+        .collect();
+    for c in (0..=0x10FFFF).filter_map(std::char::from_u32) {
+        let script = script_of(c as u32);
+        let (_, set) = sets.iter().find(|(s, _)| *s == script).unwrap();
+        assert!(set.get().contains(c), "U+{:04X}", c as u32);
+    }
+}
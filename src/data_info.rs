@@ -0,0 +1,51 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! Describes the build of the crate and the data compiled into it.
+
+// Generated by build.rs: UCD_VERSION, DATA_KEYS and DATA_CHECKSUM.
+include!(concat!(env!("OUT_DIR"), "/data_info.rs"));
+
+/// The version of this crate.
+pub fn crate_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// The version of the Unicode Character Database the data was built from,
+/// such as "14.0.0". build.rs reads it from the sources named in the
+/// headers of the data files, and fails unless they all agree on it.
+pub fn unicode_version() -> &'static str {
+    UCD_VERSION
+}
+
+/// The keys of the property data in the static blob, such as "Alpha" or
+/// "gc=Lu".
+pub fn data_keys() -> &'static [&'static str] {
+    DATA_KEYS
+}
+
+/// A checksum of the data compiled into the crate: the static blob and
+/// the contents of the data files that build.rs generates tables from.
+/// Two builds with the same checksum carry the same data, however the
+/// tables are formatted.
+pub fn data_checksum() -> u64 {
+    DATA_CHECKSUM
+}
+
+#[test]
+fn test_data_info() {
+    assert_eq!(unicode_version(), "14.0.0");
+    assert!(!crate_version().is_empty());
+
+    // Every property and value in the alias tables is in the blob.
+    use crate::tables::{BINARY_PROPERTIES, GENERAL_CATEGORIES, SCRIPTS};
+    let keys = BINARY_PROPERTIES
+        .iter()
+        .map(|&(key, _, _)| key)
+        .chain(GENERAL_CATEGORIES.iter().map(|&(key, _, _)| key))
+        .chain(SCRIPTS.iter().map(|&(key, _, _)| key));
+    for key in keys {
+        assert!(data_keys().contains(&key), "{} is not in the blob", key);
+    }
+    assert!(data_keys().contains(&"sc=Latn"));
+}
@@ -0,0 +1,32 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+use crate::tables::StringProperty;
+use crate::ICU4XStringSet;
+use icu_uniset::UnicodeSetBuilder;
+
+// Generated by build.rs from data/emoji-sequences.txt and
+// data/emoji-zwj-sequences.txt.
+include!(concat!(env!("OUT_DIR"), "/emoji.rs"));
+
+/// Returns the code points and strings of a property of strings.
+///
+/// RGI_Emoji has no entries of its own: it is the union of the other
+/// properties.
+pub(crate) fn get_string_property_set(prop: StringProperty) -> ICU4XStringSet {
+    let includes = |entry: StringProperty| entry == prop || prop == StringProperty::RgiEmoji;
+
+    let mut builder = UnicodeSetBuilder::new();
+    for (entry, start, end) in EMOJI_CODE_POINTS {
+        if includes(*entry) {
+            builder.add_range(&(*start..=*end));
+        }
+    }
+    let strings = EMOJI_SEQUENCES
+        .iter()
+        .filter(|(entry, _)| includes(*entry))
+        .map(|(_, string)| string.encode_utf16().collect())
+        .collect();
+
+    ICU4XStringSet::new(builder.build().into(), strings)
+}
@@ -0,0 +1,279 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+use std::fmt;
+
+/// The reasons a property expression like `\p{Name=Value}` can fail to
+/// resolve to a set.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PropertyError {
+    /// The property name is not in Table 69 or Table 70, and is not a
+    /// General_Category value.
+    UnknownName = 1,
+    /// The property name is enumerated, but the value is not one of its
+    /// values.
+    UnknownValue = 2,
+    /// The property is binary or a General_Category value, but a value
+    /// was given (`\p{Alpha=Yes}`).
+    ValueNotAllowed = 3,
+    /// The property is enumerated, but no value was given (`\p{Script}`).
+    ValueRequired = 4,
+    /// The property is supported, but its data is missing from the
+    /// static data.
+    DataUnavailable = 5,
+}
+
+impl PropertyError {
+    fn message(self) -> &'static str {
+        match self {
+            PropertyError::UnknownName => "unknown property name",
+            PropertyError::UnknownValue => "unknown property value",
+            PropertyError::ValueNotAllowed => "property does not take a value",
+            PropertyError::ValueRequired => "property requires a value",
+            PropertyError::DataUnavailable => "property data is unavailable",
+        }
+    }
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for PropertyError {}
+
+/// The reasons a ClassSetExpression of a RegExp with the `v` flag can
+/// fail to evaluate to a set.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassSetError {
+    /// A ClassSetRange whose start is greater than its end (`[z-a]`).
+    RangeOutOfOrder = 1,
+    /// A complemented class contains strings (`[^\q{ab}]`).
+    NegatedStrings = 2,
+    /// An intersection or subtraction with an operand that is a range, a
+    /// union or another operation, without a nested class around it
+    /// (`[a&&b--c]`, `[ab&&c]`).
+    MixedOperators = 3,
+}
+
+impl ClassSetError {
+    fn message(self) -> &'static str {
+        match self {
+            ClassSetError::RangeOutOfOrder => "range out of order in character class",
+            ClassSetError::NegatedStrings => "negated character class may contain strings",
+            ClassSetError::MixedOperators => "mixed set operations in character class",
+        }
+    }
+}
+
+impl fmt::Display for ClassSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for ClassSetError {}
+
+/// The kinds of errors in a property escape like `\p{Name=Value}`. The
+/// first five are the [`PropertyError`]s, with the same values.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PropertyEscapeErrorKind {
+    UnknownName = 1,
+    UnknownValue = 2,
+    ValueNotAllowed = 3,
+    ValueRequired = 4,
+    DataUnavailable = 5,
+    /// The `\p` is not followed by `{`.
+    MissingOpenBrace = 6,
+    /// The pattern ends before the closing `}`.
+    MissingCloseBrace = 7,
+    /// A character that cannot appear at this point of the escape, such
+    /// as a non-ASCII character, or a `}` after an empty name.
+    InvalidCharacter = 8,
+    /// A `\P` of a property of strings.
+    NegatedStrings = 9,
+}
+
+impl PropertyEscapeErrorKind {
+    /// The message of the kind, which is that of the [`PropertyError`]
+    /// for the first five.
+    fn message(self) -> &'static str {
+        match self {
+            PropertyEscapeErrorKind::UnknownName => PropertyError::UnknownName.message(),
+            PropertyEscapeErrorKind::UnknownValue => PropertyError::UnknownValue.message(),
+            PropertyEscapeErrorKind::ValueNotAllowed => PropertyError::ValueNotAllowed.message(),
+            PropertyEscapeErrorKind::ValueRequired => PropertyError::ValueRequired.message(),
+            PropertyEscapeErrorKind::DataUnavailable => PropertyError::DataUnavailable.message(),
+            PropertyEscapeErrorKind::MissingOpenBrace => "missing { in property escape",
+            PropertyEscapeErrorKind::MissingCloseBrace => "missing } in property escape",
+            PropertyEscapeErrorKind::InvalidCharacter => "invalid character in property escape",
+            PropertyEscapeErrorKind::NegatedStrings => "negated property of strings",
+        }
+    }
+}
+
+impl From<PropertyError> for PropertyEscapeErrorKind {
+    fn from(error: PropertyError) -> Self {
+        match error {
+            PropertyError::UnknownName => PropertyEscapeErrorKind::UnknownName,
+            PropertyError::UnknownValue => PropertyEscapeErrorKind::UnknownValue,
+            PropertyError::ValueNotAllowed => PropertyEscapeErrorKind::ValueNotAllowed,
+            PropertyError::ValueRequired => PropertyEscapeErrorKind::ValueRequired,
+            PropertyError::DataUnavailable => PropertyEscapeErrorKind::DataUnavailable,
+        }
+    }
+}
+
+/// An error in a property escape, and the offset in the pattern of the
+/// code unit where it was found.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PropertyEscapeError {
+    pub kind: PropertyEscapeErrorKind,
+    pub offset: usize,
+}
+
+impl fmt::Display for PropertyEscapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.kind.message(), self.offset)
+    }
+}
+
+impl std::error::Error for PropertyEscapeError {}
+
+/// The kinds of errors in a CharacterClass. The first nine are the
+/// [`PropertyEscapeErrorKind`]s of its property escapes, with the same
+/// values.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassErrorKind {
+    UnknownName = 1,
+    UnknownValue = 2,
+    ValueNotAllowed = 3,
+    ValueRequired = 4,
+    DataUnavailable = 5,
+    MissingOpenBrace = 6,
+    /// The pattern ends before the `}` of a property escape or `\q{…}`.
+    MissingCloseBrace = 7,
+    InvalidCharacter = 8,
+    /// A complemented class or property escape may contain strings.
+    NegatedStrings = 9,
+    /// The pattern ends before the closing `]`.
+    MissingCloseBracket = 10,
+    /// An escape that is not allowed in a class, or is malformed.
+    InvalidEscape = 11,
+    /// A range whose start is greater than its end (`[z-a]`).
+    RangeOutOfOrder = 12,
+    /// A range whose start or end is not a single character (`[\d-z]`).
+    InvalidRangeEndpoint = 13,
+    /// With the `v` flag, a ClassSetSyntaxCharacter or a
+    /// ClassSetReservedDoublePunctuator that is not escaped (`[(]`).
+    SyntaxCharacter = 14,
+    /// With the `v` flag, a `&&` or `--` that is missing an operand, or
+    /// mixed with other operators (`[a&&b--c]`).
+    InvalidOperation = 15,
+    /// Neither the `u` nor the `v` flag is set.
+    UnsupportedFlags = 16,
+}
+
+impl ClassErrorKind {
+    /// The message of the kind. The kinds of property escapes have the
+    /// messages of their [`PropertyEscapeErrorKind`], except that a `}` may
+    /// also be missing from a `\q{…}`, and the kinds of evaluation have
+    /// those of their [`ClassSetError`].
+    fn message(self) -> &'static str {
+        match self {
+            ClassErrorKind::UnknownName => PropertyEscapeErrorKind::UnknownName.message(),
+            ClassErrorKind::UnknownValue => PropertyEscapeErrorKind::UnknownValue.message(),
+            ClassErrorKind::ValueNotAllowed => PropertyEscapeErrorKind::ValueNotAllowed.message(),
+            ClassErrorKind::ValueRequired => PropertyEscapeErrorKind::ValueRequired.message(),
+            ClassErrorKind::DataUnavailable => PropertyEscapeErrorKind::DataUnavailable.message(),
+            ClassErrorKind::MissingOpenBrace => PropertyEscapeErrorKind::MissingOpenBrace.message(),
+            ClassErrorKind::MissingCloseBrace => "missing }",
+            ClassErrorKind::InvalidCharacter => PropertyEscapeErrorKind::InvalidCharacter.message(),
+            ClassErrorKind::NegatedStrings => ClassSetError::NegatedStrings.message(),
+            ClassErrorKind::MissingCloseBracket => "missing ] in character class",
+            ClassErrorKind::InvalidEscape => "invalid escape in character class",
+            ClassErrorKind::RangeOutOfOrder => ClassSetError::RangeOutOfOrder.message(),
+            ClassErrorKind::InvalidRangeEndpoint => "invalid character class range",
+            ClassErrorKind::SyntaxCharacter => "unescaped syntax character in character class",
+            ClassErrorKind::InvalidOperation => "invalid set operation in character class",
+            ClassErrorKind::UnsupportedFlags => "character class requires the u or v flag",
+        }
+    }
+}
+
+impl From<PropertyEscapeErrorKind> for ClassErrorKind {
+    fn from(kind: PropertyEscapeErrorKind) -> Self {
+        match kind {
+            PropertyEscapeErrorKind::UnknownName => ClassErrorKind::UnknownName,
+            PropertyEscapeErrorKind::UnknownValue => ClassErrorKind::UnknownValue,
+            PropertyEscapeErrorKind::ValueNotAllowed => ClassErrorKind::ValueNotAllowed,
+            PropertyEscapeErrorKind::ValueRequired => ClassErrorKind::ValueRequired,
+            PropertyEscapeErrorKind::DataUnavailable => ClassErrorKind::DataUnavailable,
+            PropertyEscapeErrorKind::MissingOpenBrace => ClassErrorKind::MissingOpenBrace,
+            PropertyEscapeErrorKind::MissingCloseBrace => ClassErrorKind::MissingCloseBrace,
+            PropertyEscapeErrorKind::InvalidCharacter => ClassErrorKind::InvalidCharacter,
+            PropertyEscapeErrorKind::NegatedStrings => ClassErrorKind::NegatedStrings,
+        }
+    }
+}
+
+impl From<PropertyError> for ClassErrorKind {
+    fn from(error: PropertyError) -> Self {
+        PropertyEscapeErrorKind::from(error).into()
+    }
+}
+
+impl From<ClassSetError> for ClassErrorKind {
+    fn from(error: ClassSetError) -> Self {
+        match error {
+            ClassSetError::RangeOutOfOrder => ClassErrorKind::RangeOutOfOrder,
+            ClassSetError::NegatedStrings => ClassErrorKind::NegatedStrings,
+            ClassSetError::MixedOperators => ClassErrorKind::InvalidOperation,
+        }
+    }
+}
+
+/// An error in a CharacterClass, and the offset in the pattern of the code
+/// unit where it was found.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClassError {
+    pub kind: ClassErrorKind,
+    pub offset: usize,
+}
+
+impl fmt::Display for ClassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.kind.message(), self.offset)
+    }
+}
+
+impl std::error::Error for ClassError {}
+
+#[test]
+fn test_error_messages() {
+    let escape = PropertyEscapeError {
+        kind: PropertyError::UnknownValue.into(),
+        offset: 3,
+    };
+    assert_eq!(escape.to_string(), "unknown property value at offset 3");
+    let class = ClassError {
+        kind: ClassSetError::RangeOutOfOrder.into(),
+        offset: 1,
+    };
+    assert_eq!(
+        class.to_string(),
+        "range out of order in character class at offset 1"
+    );
+    let class = ClassError {
+        kind: PropertyEscapeErrorKind::MissingCloseBrace.into(),
+        offset: 5,
+    };
+    assert_eq!(class.to_string(), "missing } at offset 5");
+}
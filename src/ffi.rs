@@ -5,7 +5,18 @@ use std::ffi::CStr;
 use std::os::raw::c_char;
 use std::ptr;
 
-use crate::ICU4XUniset;
+use crate::canonicalize::CanonicalizeMode;
+use crate::catalog::{self, PropertyInfo, PropertyKind, PropertyValueInfo};
+use crate::class_escape::{ClassEscape, RegExpFlags};
+use crate::class_set::ClassSetExpression;
+use crate::code_point_props;
+use crate::string_matcher::{StringSetMatcher, StringTrieNode};
+use crate::tables::GENERAL_CATEGORIES;
+use crate::{
+    ClassError, ClassSetError, ICU4XStringSet, ICU4XUniset, MatchingMode, PropertyError,
+    PropertyEscapeError, PropertyId,
+};
+use icu_uniset::enum_props::Script;
 use icu_uniset::UnicodeSetBuilder;
 
 unsafe fn ptr_to_str<'a>(raw: *const c_char) -> Option<&'a str> {
@@ -15,6 +26,53 @@ unsafe fn ptr_to_str<'a>(raw: *const c_char) -> Option<&'a str> {
     CStr::from_ptr(raw).to_str().ok()
 }
 
+/// Returns a pointer to the UTF-8 bytes of `name`, which are not
+/// NUL-terminated, and stores their length in `*length`.
+unsafe fn str_to_ptr(name: Option<&'static str>, length: *mut usize) -> *const c_char {
+    let (name, len) = match name {
+        Some(name) => (name.as_ptr() as *const c_char, name.len()),
+        None => (ptr::null(), 0),
+    };
+    if !length.is_null() {
+        *length = len;
+    }
+    name
+}
+
+/// Boxes the set in `result`, or stores the error in `*error` and
+/// returns null.
+unsafe fn into_raw_or_error<T, E>(result: Result<T, E>, error: *mut E) -> *mut T {
+    match result {
+        Ok(set) => Box::into_raw(Box::new(set)),
+        Err(e) => {
+            if !error.is_null() {
+                *error = e;
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Returns the slice of `length` code units at `data`, which may be null
+/// if `length` is 0.
+unsafe fn utf16_slice<'a>(data: *const u16, length: usize) -> &'a [u16] {
+    if length == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data, length)
+    }
+}
+
+/// Returns the slice of `length` Latin1 characters at `data`, which may
+/// be null if `length` is 0.
+unsafe fn latin1_slice<'a>(data: *const u8, length: usize) -> &'a [u8] {
+    if length == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data, length)
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn icu4x_uniset_create_for_property(
     prop_name: *const c_char,
@@ -32,6 +90,31 @@ pub unsafe extern "C" fn icu4x_uniset_create_for_property(
     }
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_uniset_try_create_for_property(
+    prop_name: *const c_char,
+    prop_value: *const c_char,
+    error: *mut PropertyError,
+) -> *mut ICU4XUniset {
+    let result = match ptr_to_str(prop_name) {
+        Some(prop_name) => crate::property::try_get_unicode_set(prop_name, ptr_to_str(prop_value)),
+        None => Err(PropertyError::UnknownName),
+    };
+
+    into_raw_or_error(result, error)
+}
+
+/// Returns the set matched by a CharacterClassEscape or `.` in a RegExp
+/// with `flags`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_uniset_create_for_class_escape(
+    escape: ClassEscape,
+    flags: RegExpFlags,
+    error: *mut PropertyError,
+) -> *mut ICU4XUniset {
+    into_raw_or_error(crate::class_escape::class_escape_set(escape, flags), error)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn icu4x_uniset_complemented(uniset: *mut ICU4XUniset) -> *mut ICU4XUniset {
     let set = Box::from_raw(uniset);
@@ -41,6 +124,15 @@ pub unsafe extern "C" fn icu4x_uniset_complemented(uniset: *mut ICU4XUniset) ->
     Box::into_raw(Box::new(builder.build().into()))
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_uniset_case_closed(
+    uniset: *mut ICU4XUniset,
+    mode: CanonicalizeMode,
+) -> *mut ICU4XUniset {
+    let set = Box::from_raw(uniset);
+    Box::into_raw(Box::new(crate::canonicalize::case_close(&set, mode)))
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn icu4x_uniset_get_range_count(uniset: *const ICU4XUniset) -> usize {
     (&*uniset).get().get_range_count()
@@ -74,3 +166,795 @@ pub unsafe extern "C" fn icu4x_uniset_get_range_end(
 pub unsafe extern "C" fn icu4x_uniset_destroy(uniset: *mut ICU4XUniset) {
     let _ = Box::from_raw(uniset);
 }
+
+/// Looks up a property for a RegExp with the `v` flag, which may be a
+/// property of strings.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_try_create_for_property(
+    prop_name: *const c_char,
+    prop_value: *const c_char,
+    error: *mut PropertyError,
+) -> *mut ICU4XStringSet {
+    let result = match ptr_to_str(prop_name) {
+        Some(prop_name) => crate::property::try_get_string_set_with_mode(
+            prop_name,
+            ptr_to_str(prop_value),
+            MatchingMode::Ecma262,
+        ),
+        None => Err(PropertyError::UnknownName),
+    };
+
+    into_raw_or_error(result, error)
+}
+
+/// The returned set is owned by `set`, and must not be destroyed.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_get_code_points(
+    set: *const ICU4XStringSet,
+) -> *const ICU4XUniset {
+    (&*set).code_points()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_get_string_count(set: *const ICU4XStringSet) -> usize {
+    (&*set).strings().len()
+}
+
+/// Returns the length in UTF-16 code units of the string at `index`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_get_string_length(
+    set: *const ICU4XStringSet,
+    index: usize,
+) -> usize {
+    (&*set)
+        .strings()
+        .get(index)
+        .map(|string| string.len())
+        .unwrap_or(0)
+}
+
+/// Copies as much of the string at `index` as fits into `buffer`, and
+/// returns the number of UTF-16 code units copied. `buffer` may be null
+/// if `capacity` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_copy_string(
+    set: *const ICU4XStringSet,
+    index: usize,
+    buffer: *mut u16,
+    capacity: usize,
+) -> usize {
+    let string = match (&*set).strings().get(index) {
+        Some(string) => string,
+        None => return 0,
+    };
+    let len = string.len().min(capacity);
+    if len == 0 || buffer.is_null() {
+        return 0;
+    }
+    ptr::copy_nonoverlapping(string.as_ptr(), buffer, len);
+    len
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_destroy(set: *mut ICU4XStringSet) {
+    let _ = Box::from_raw(set);
+}
+
+fn get_property(property: usize) -> Option<&'static PropertyInfo> {
+    catalog::properties().get(property)
+}
+
+fn get_property_value(property: usize, value: usize) -> Option<&'static PropertyValueInfo> {
+    get_property(property).and_then(|info| info.values().get(value))
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_property_catalog_get_property_count() -> usize {
+    catalog::properties().len()
+}
+
+/// Returns `PropertyKind::Binary` if `property` is out of range.
+#[no_mangle]
+pub extern "C" fn icu4x_property_catalog_get_property_kind(property: usize) -> PropertyKind {
+    get_property(property).map_or(PropertyKind::Binary, |info| info.kind())
+}
+
+/// Returns the number of names of `property`, including its canonical
+/// name.
+#[no_mangle]
+pub extern "C" fn icu4x_property_catalog_get_name_count(property: usize) -> usize {
+    get_property(property).map_or(0, |info| info.names().len())
+}
+
+/// Name 0 is the canonical name, and the others are aliases.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_property_catalog_get_name(
+    property: usize,
+    name: usize,
+    length: *mut usize,
+) -> *const c_char {
+    let name = get_property(property).and_then(|info| info.names().get(name).copied());
+    str_to_ptr(name, length)
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_property_catalog_get_value_count(property: usize) -> usize {
+    get_property(property).map_or(0, |info| info.values().len())
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_property_catalog_get_value_name_count(
+    property: usize,
+    value: usize,
+) -> usize {
+    get_property_value(property, value).map_or(0, |info| info.names().len())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_property_catalog_get_value_name(
+    property: usize,
+    value: usize,
+    name: usize,
+    length: *mut usize,
+) -> *const c_char {
+    let name = get_property_value(property, value).and_then(|info| info.names().get(name).copied());
+    str_to_ptr(name, length)
+}
+
+/// Returns the closest valid spelling of a rejected property name, or of
+/// its value, or null if there is none. See `suggest_property_names`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_property_suggest(
+    prop_name: *const c_char,
+    prop_value: *const c_char,
+    length: *mut usize,
+) -> *const c_char {
+    let suggestion = ptr_to_str(prop_name).and_then(|prop_name| {
+        crate::suggest::suggest_property_names(prop_name, ptr_to_str(prop_value))
+            .first()
+            .copied()
+    });
+    str_to_ptr(suggestion, length)
+}
+
+/// Resolves a property name and optional value, and stores its ID in
+/// `*id`. On failure, stores the reason in `*error` and returns false.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_property_resolve(
+    prop_name: *const c_char,
+    prop_value: *const c_char,
+    v_mode: bool,
+    id: *mut PropertyId,
+    error: *mut PropertyError,
+) -> bool {
+    let result = match ptr_to_str(prop_name) {
+        Some(prop_name) => crate::property::resolve_property(
+            prop_name,
+            ptr_to_str(prop_value),
+            MatchingMode::Ecma262,
+            v_mode,
+        ),
+        None => Err(PropertyError::UnknownName),
+    };
+
+    match result {
+        Ok(result) => {
+            *id = result;
+            true
+        }
+        Err(e) => {
+            if !error.is_null() {
+                *error = e;
+            }
+            false
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_uniset_create_for_property_id(
+    id: PropertyId,
+    error: *mut PropertyError,
+) -> *mut ICU4XUniset {
+    into_raw_or_error(crate::property::get_property_unicode_set(id), error)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_create_for_property_id(
+    id: PropertyId,
+    error: *mut PropertyError,
+) -> *mut ICU4XStringSet {
+    into_raw_or_error(crate::property::get_property_set(id), error)
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_property_id_may_contain_strings(id: PropertyId) -> bool {
+    id.may_contain_strings()
+}
+
+/// Returns the General_Category value of a code point, as one of the
+/// values of ICU's `UCharCategory`.
+#[no_mangle]
+pub extern "C" fn icu4x_general_category_of(code_point: u32) -> u8 {
+    code_point_props::general_category_of(code_point) as u8
+}
+
+/// Returns the Script value of a code point, as one of the values of
+/// ICU's `UScriptCode`.
+#[no_mangle]
+pub extern "C" fn icu4x_script_of(code_point: u32) -> u16 {
+    code_point_props::script_of(code_point).0
+}
+
+fn general_category_names(gc: u8) -> Option<(&'static str, &'static str)> {
+    GENERAL_CATEGORIES
+        .iter()
+        .map(|&(_, _, category)| category)
+        .find(|&category| category as u8 == gc)
+        .and_then(code_point_props::general_category_names)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_general_category_get_long_name(
+    gc: u8,
+    length: *mut usize,
+) -> *const c_char {
+    str_to_ptr(general_category_names(gc).map(|(long, _)| long), length)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_general_category_get_short_name(
+    gc: u8,
+    length: *mut usize,
+) -> *const c_char {
+    str_to_ptr(general_category_names(gc).map(|(_, short)| short), length)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_script_get_long_name(
+    script: u16,
+    length: *mut usize,
+) -> *const c_char {
+    let names = code_point_props::script_names(Script(script));
+    str_to_ptr(names.map(|(long, _)| long), length)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_script_get_short_name(
+    script: u16,
+    length: *mut usize,
+) -> *const c_char {
+    let names = code_point_props::script_names(Script(script));
+    str_to_ptr(names.map(|(_, short)| short), length)
+}
+
+/// Copies as many of the Script_Extensions values of a code point as fit
+/// into `buffer`, and returns the number of values.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_script_extensions_of(
+    code_point: u32,
+    buffer: *mut u16,
+    capacity: usize,
+) -> usize {
+    let scripts = crate::script_extensions::script_extensions_of(code_point);
+    for (i, script) in scripts.iter().take(capacity).enumerate() {
+        *buffer.add(i) = script.0;
+    }
+    scripts.len()
+}
+
+/// Canonicalize for RegExps with the `u` or `v` flag.
+#[no_mangle]
+pub extern "C" fn icu4x_canonicalize_unicode(code_point: u32) -> u32 {
+    crate::canonicalize::canonicalize_unicode(code_point)
+}
+
+/// Canonicalize for RegExps without the `u` or `v` flag.
+#[no_mangle]
+pub extern "C" fn icu4x_canonicalize_legacy(code_unit: u16) -> u16 {
+    crate::canonicalize::canonicalize_legacy(code_unit)
+}
+
+// Flags of `icu4x_case_class_get_flags`.
+const CASE_CLASS_MIXES_ASCII: u32 = 1;
+const CASE_CLASS_MIXES_BMP_AND_SUPPLEMENTARY: u32 = 2;
+
+#[no_mangle]
+pub extern "C" fn icu4x_case_class_get_count(mode: CanonicalizeMode) -> usize {
+    crate::canonicalize::case_classes(mode).len()
+}
+
+/// Copies as many of the code points of a case class as fit into
+/// `buffer`, which may be null if `capacity` is 0, and returns the number
+/// of code points.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_case_class_get_code_points(
+    mode: CanonicalizeMode,
+    index: usize,
+    buffer: *mut u32,
+    capacity: usize,
+) -> usize {
+    let code_points = match crate::canonicalize::case_classes(mode).get(index) {
+        Some(class) => class.code_points(),
+        None => return 0,
+    };
+    let len = code_points.len().min(capacity);
+    if len == 0 {
+        return code_points.len();
+    }
+    ptr::copy_nonoverlapping(code_points.as_ptr(), buffer, len);
+    code_points.len()
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_case_class_get_canonical(mode: CanonicalizeMode, index: usize) -> u32 {
+    crate::canonicalize::case_classes(mode)
+        .get(index)
+        .map_or(0, |class| class.canonical())
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_case_class_get_flags(mode: CanonicalizeMode, index: usize) -> u32 {
+    let class = match crate::canonicalize::case_classes(mode).get(index) {
+        Some(class) => class,
+        None => return 0,
+    };
+    let mut flags = 0;
+    if class.mixes_ascii() {
+        flags |= CASE_CLASS_MIXES_ASCII;
+    }
+    if class.mixes_bmp_and_supplementary() {
+        flags |= CASE_CLASS_MIXES_BMP_AND_SUPPLEMENTARY;
+    }
+    flags
+}
+
+/// Compares two UTF-16 strings as a case-insensitive backreference does.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_case_insensitive_equals_utf16(
+    a: *const u16,
+    a_length: usize,
+    b: *const u16,
+    b_length: usize,
+    mode: CanonicalizeMode,
+) -> bool {
+    crate::canonicalize::case_insensitive_equals_utf16(
+        utf16_slice(a, a_length),
+        utf16_slice(b, b_length),
+        mode,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_is_identifier_start(code_point: u32) -> bool {
+    crate::identifier::is_identifier_start(code_point)
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_is_identifier_part(code_point: u32) -> bool {
+    crate::identifier::is_identifier_part(code_point)
+}
+
+/// Validates the source text of a capture group name, between `<` and
+/// `>`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_is_valid_group_name_utf16(name: *const u16, length: usize) -> bool {
+    crate::identifier::is_valid_group_name_utf16(utf16_slice(name, length))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_get_crate_version(length: *mut usize) -> *const c_char {
+    str_to_ptr(Some(crate::data_info::crate_version()), length)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_get_unicode_version(length: *mut usize) -> *const c_char {
+    str_to_ptr(Some(crate::data_info::unicode_version()), length)
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_get_data_key_count() -> usize {
+    crate::data_info::data_keys().len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_get_data_key(index: usize, length: *mut usize) -> *const c_char {
+    str_to_ptr(crate::data_info::data_keys().get(index).copied(), length)
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_get_data_checksum() -> u64 {
+    crate::data_info::data_checksum()
+}
+
+fn expression_into_raw(expression: ClassSetExpression) -> *mut ClassSetExpression {
+    Box::into_raw(Box::new(expression))
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_class_set_expression_create_character(
+    code_point: u32,
+) -> *mut ClassSetExpression {
+    expression_into_raw(ClassSetExpression::Character(code_point))
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_class_set_expression_create_range(
+    start: u32,
+    end: u32,
+) -> *mut ClassSetExpression {
+    expression_into_raw(ClassSetExpression::Range(start, end))
+}
+
+/// The `i`th of the `count` ClassStrings is the `lengths[i]` UTF-16 code
+/// units at `strings[i]`, which may be null if the length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_create_string_disjunction(
+    strings: *const *const u16,
+    lengths: *const usize,
+    count: usize,
+) -> *mut ClassSetExpression {
+    let strings = (0..count)
+        .map(|i| utf16_slice(*strings.add(i), *lengths.add(i)).to_vec())
+        .collect();
+    expression_into_raw(ClassSetExpression::ClassStringDisjunction(strings))
+}
+
+/// Takes ownership of `set`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_create_for_string_set(
+    set: *mut ICU4XStringSet,
+) -> *mut ClassSetExpression {
+    expression_into_raw(ClassSetExpression::Set(*Box::from_raw(set)))
+}
+
+/// Takes ownership of `set`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_create_for_uniset(
+    set: *mut ICU4XUniset,
+) -> *mut ClassSetExpression {
+    expression_into_raw(ClassSetExpression::Set((*Box::from_raw(set)).into()))
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_class_set_expression_create_union() -> *mut ClassSetExpression {
+    expression_into_raw(ClassSetExpression::Union(vec![]))
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_class_set_expression_create_intersection() -> *mut ClassSetExpression {
+    expression_into_raw(ClassSetExpression::Intersection(vec![]))
+}
+
+#[no_mangle]
+pub extern "C" fn icu4x_class_set_expression_create_subtraction() -> *mut ClassSetExpression {
+    expression_into_raw(ClassSetExpression::Subtraction(vec![]))
+}
+
+/// Takes ownership of `operand`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_create_nested(
+    operand: *mut ClassSetExpression,
+) -> *mut ClassSetExpression {
+    expression_into_raw(ClassSetExpression::Nested(Box::from_raw(operand)))
+}
+
+/// Takes ownership of `operand`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_create_complement(
+    operand: *mut ClassSetExpression,
+) -> *mut ClassSetExpression {
+    expression_into_raw(ClassSetExpression::Complement(Box::from_raw(operand)))
+}
+
+/// Appends `operand` to a union, intersection or subtraction, taking
+/// ownership of it. Returns false, and destroys `operand`, if `expression`
+/// is any other kind of expression.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_add_operand(
+    expression: *mut ClassSetExpression,
+    operand: *mut ClassSetExpression,
+) -> bool {
+    let operand = *Box::from_raw(operand);
+    match &mut *expression {
+        ClassSetExpression::Union(operands)
+        | ClassSetExpression::Intersection(operands)
+        | ClassSetExpression::Subtraction(operands) => {
+            operands.push(operand);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_may_contain_strings(
+    expression: *const ClassSetExpression,
+) -> bool {
+    (&*expression).may_contain_strings()
+}
+
+/// Returns false, and stores the first early error in `*error`, if the
+/// expression is invalid.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_validate(
+    expression: *const ClassSetExpression,
+    error: *mut ClassSetError,
+) -> bool {
+    match (&*expression).validate() {
+        Ok(()) => true,
+        Err(e) => {
+            if !error.is_null() {
+                *error = e;
+            }
+            false
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_evaluate(
+    expression: *const ClassSetExpression,
+    ignore_case: bool,
+    error: *mut ClassSetError,
+) -> *mut ICU4XStringSet {
+    into_raw_or_error((&*expression).evaluate(ignore_case), error)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_class_set_expression_destroy(expression: *mut ClassSetExpression) {
+    let _ = Box::from_raw(expression);
+}
+
+/// Parses a property escape in a Latin1 or UTF-16 pattern, where `cursor`
+/// is the offset just after the `\p` or `\P`.
+unsafe fn parse_property_escape<T: Copy + Into<u32>>(
+    pattern: &[T],
+    cursor: usize,
+    negated: bool,
+    v_mode: bool,
+    consumed: *mut usize,
+    error: *mut PropertyEscapeError,
+) -> *mut ICU4XStringSet {
+    let result = crate::property_escape::parse_property_escape(pattern, cursor, negated, v_mode)
+        .map(|(set, length)| {
+            if !consumed.is_null() {
+                *consumed = length;
+            }
+            set
+        });
+    into_raw_or_error(result, error)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_parse_property_escape_latin1(
+    pattern: *const u8,
+    length: usize,
+    cursor: usize,
+    negated: bool,
+    v_mode: bool,
+    consumed: *mut usize,
+    error: *mut PropertyEscapeError,
+) -> *mut ICU4XStringSet {
+    let pattern = latin1_slice(pattern, length);
+    parse_property_escape(pattern, cursor, negated, v_mode, consumed, error)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_parse_property_escape_utf16(
+    pattern: *const u16,
+    length: usize,
+    cursor: usize,
+    negated: bool,
+    v_mode: bool,
+    consumed: *mut usize,
+    error: *mut PropertyEscapeError,
+) -> *mut ICU4XStringSet {
+    let pattern = utf16_slice(pattern, length);
+    parse_property_escape(pattern, cursor, negated, v_mode, consumed, error)
+}
+
+/// Compiles a class in a Latin1 or UTF-16 pattern, where `cursor` is the
+/// offset just after the `[`.
+unsafe fn compile_class<T: Copy + Into<u32>>(
+    pattern: &[T],
+    cursor: usize,
+    flags: RegExpFlags,
+    consumed: *mut usize,
+    error: *mut ClassError,
+) -> *mut ICU4XStringSet {
+    let result =
+        crate::class_compiler::compile_class(pattern, cursor, flags).map(|(set, length)| {
+            if !consumed.is_null() {
+                *consumed = length;
+            }
+            set
+        });
+    into_raw_or_error(result, error)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_compile_class_latin1(
+    pattern: *const u8,
+    length: usize,
+    cursor: usize,
+    flags: RegExpFlags,
+    consumed: *mut usize,
+    error: *mut ClassError,
+) -> *mut ICU4XStringSet {
+    compile_class(
+        latin1_slice(pattern, length),
+        cursor,
+        flags,
+        consumed,
+        error,
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_compile_class_utf16(
+    pattern: *const u16,
+    length: usize,
+    cursor: usize,
+    flags: RegExpFlags,
+    consumed: *mut usize,
+    error: *mut ClassError,
+) -> *mut ICU4XStringSet {
+    compile_class(utf16_slice(pattern, length), cursor, flags, consumed, error)
+}
+
+/// Takes ownership of `set`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_matcher_create(
+    set: *mut ICU4XStringSet,
+    ignore_case: bool,
+) -> *mut StringSetMatcher {
+    Box::into_raw(Box::new(StringSetMatcher::new(
+        *Box::from_raw(set),
+        ignore_case,
+    )))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_matcher_get_alternative_count(
+    matcher: *const StringSetMatcher,
+) -> usize {
+    (&*matcher).alternatives().len()
+}
+
+/// Returns a pointer to the UTF-16 code units of the alternative at
+/// `index`, and stores their length in `*length`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_matcher_get_alternative(
+    matcher: *const StringSetMatcher,
+    index: usize,
+    length: *mut usize,
+) -> *const u16 {
+    let (alternative, len) = match (&*matcher).alternatives().get(index) {
+        Some(alternative) => (alternative.as_ptr(), alternative.len()),
+        None => (ptr::null(), 0),
+    };
+    if !length.is_null() {
+        *length = len;
+    }
+    alternative
+}
+
+/// The returned set is owned by `matcher`, and must not be destroyed.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_matcher_get_code_points(
+    matcher: *const StringSetMatcher,
+) -> *const ICU4XUniset {
+    (&*matcher).code_points()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_matcher_matches_empty(
+    matcher: *const StringSetMatcher,
+) -> bool {
+    (&*matcher).matches_empty()
+}
+
+/// Returns a pointer to the nodes of the trie, and stores their number in
+/// `*length`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_matcher_get_trie(
+    matcher: *const StringSetMatcher,
+    length: *mut usize,
+) -> *const StringTrieNode {
+    let trie = (&*matcher).trie();
+    if !length.is_null() {
+        *length = trie.len();
+    }
+    trie.as_ptr()
+}
+
+/// Returns false if nothing matches at `pos`. Otherwise, stores the length
+/// of the longest match in `*matched`.
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_matcher_longest_match(
+    matcher: *const StringSetMatcher,
+    input: *const u16,
+    length: usize,
+    pos: usize,
+    matched: *mut usize,
+) -> bool {
+    match (&*matcher).longest_match(utf16_slice(input, length), pos) {
+        Some(len) => {
+            if !matched.is_null() {
+                *matched = len;
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icu4x_string_set_matcher_destroy(matcher: *mut StringSetMatcher) {
+    let _ = Box::from_raw(matcher);
+}
+
+#[test]
+fn test_class_set_expression_builder() {
+    let disjunction = |strings: &[&[u16]]| unsafe {
+        let pointers: Vec<_> = strings.iter().map(|s| s.as_ptr()).collect();
+        let lengths: Vec<_> = strings.iter().map(|s| s.len()).collect();
+        icu4x_class_set_expression_create_string_disjunction(
+            pointers.as_ptr(),
+            lengths.as_ptr(),
+            strings.len(),
+        )
+    };
+    let a = ['a' as u16];
+    let b = ['b' as u16];
+    let validate = |first| unsafe {
+        let intersection = icu4x_class_set_expression_create_intersection();
+        assert!(icu4x_class_set_expression_add_operand(intersection, first));
+        assert!(icu4x_class_set_expression_add_operand(
+            intersection,
+            icu4x_class_set_expression_create_character('c' as u32),
+        ));
+        let mut error = ClassSetError::RangeOutOfOrder;
+        let valid = icu4x_class_set_expression_validate(intersection, &mut error);
+        icu4x_class_set_expression_destroy(intersection);
+        if valid {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    };
+
+    // [\q{a|b}&&c]
+    assert_eq!(validate(disjunction(&[&a, &b])), Ok(()));
+
+    // [\q{a}\q{b}&&c]
+    let union = icu4x_class_set_expression_create_union();
+    unsafe {
+        assert!(icu4x_class_set_expression_add_operand(
+            union,
+            disjunction(&[&a])
+        ));
+        assert!(icu4x_class_set_expression_add_operand(
+            union,
+            disjunction(&[&b])
+        ));
+    }
+    assert_eq!(validate(union), Err(ClassSetError::MixedOperators));
+}
+
+#[test]
+fn test_case_class_get_code_points() {
+    use std::ptr::null_mut;
+
+    // The count is returned without copying when there is no buffer.
+    let count =
+        unsafe { icu4x_case_class_get_code_points(CanonicalizeMode::Unicode, 0, null_mut(), 0) };
+    assert!(count >= 2);
+    let mut buffer = vec![0; count];
+    let copied = unsafe {
+        icu4x_case_class_get_code_points(CanonicalizeMode::Unicode, 0, buffer.as_mut_ptr(), count)
+    };
+    assert_eq!(copied, count);
+    assert!(buffer.iter().all(|&c| c != 0));
+}
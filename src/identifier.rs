@@ -0,0 +1,192 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! [IdentifierName](https://tc39.es/ecma262/multipage/ecmascript-language-lexical-grammar.html#sec-names-and-keywords)
+//! characters, and the
+//! [RegExpIdentifierName](https://tc39.es/ecma262/multipage/text-processing.html#prod-RegExpIdentifierName)
+//! of named capture groups.
+
+use crate::property::{get_code_point_set, Property};
+use crate::tables::BinaryProperty;
+use once_cell::sync::OnceCell;
+
+/// The ranges of a binary property, which are sorted and do not overlap.
+type Ranges = Vec<(u32, u32)>;
+
+static ID_START: OnceCell<Ranges> = OnceCell::new();
+static ID_CONTINUE: OnceCell<Ranges> = OnceCell::new();
+
+fn get_ranges(cell: &'static OnceCell<Ranges>, prop: BinaryProperty) -> &'static Ranges {
+    cell.get_or_init(|| {
+        // The properties are in the static data, so this only fails if
+        // the crate was built without it.
+        get_code_point_set(Property::Binary(prop)).map_or_else(
+            |_| vec![],
+            |set| {
+                let set = set.get();
+                (0..set.get_range_count())
+                    .filter_map(|i| set.get_nth_range(i))
+                    .map(|range| (*range.start(), *range.end()))
+                    .collect()
+            },
+        )
+    })
+}
+
+fn ranges_contain(ranges: &[(u32, u32)], code_point: u32) -> bool {
+    let i = ranges.partition_point(|&(_, end)| end < code_point);
+    ranges.get(i).is_some_and(|&(start, _)| start <= code_point)
+}
+
+/// Whether `code_point` is an IdentifierStartChar: ID_Start, `$` or `_`.
+pub fn is_identifier_start(code_point: u32) -> bool {
+    if code_point < 0x80 {
+        let c = code_point as u8;
+        return c.is_ascii_alphabetic() || c == b'$' || c == b'_';
+    }
+    ranges_contain(get_ranges(&ID_START, BinaryProperty::IdStart), code_point)
+}
+
+/// Whether `code_point` is an IdentifierPartChar: ID_Continue, `$`, ZWNJ
+/// or ZWJ.
+pub fn is_identifier_part(code_point: u32) -> bool {
+    if code_point < 0x80 {
+        let c = code_point as u8;
+        return c.is_ascii_alphanumeric() || c == b'$' || c == b'_';
+    }
+    code_point == 0x200C
+        || code_point == 0x200D
+        || ranges_contain(
+            get_ranges(&ID_CONTINUE, BinaryProperty::IdContinue),
+            code_point,
+        )
+}
+
+fn parse_hex(units: &[u16]) -> Option<u32> {
+    units.iter().try_fold(0u32, |value, &unit| {
+        let digit = std::char::from_u32(unit as u32)?.to_digit(16)?;
+        value.checked_mul(16)?.checked_add(digit)
+    })
+}
+
+/// Reads the character at `*pos` of a RegExpIdentifierName, which may be
+/// written as a RegExpUnicodeEscapeSequence, and advances `*pos` past it.
+/// Returns `None` if the escape is malformed.
+fn next_code_point(name: &[u16], pos: &mut usize) -> Option<u32> {
+    let rest = &name[*pos..];
+    if rest[0] != '\\' as u16 {
+        let code_point = std::char::decode_utf16(rest.iter().copied())
+            .next()?
+            .map_or_else(|e| e.unpaired_surrogate() as u32, |c| c as u32);
+        *pos += if code_point > 0xFFFF { 2 } else { 1 };
+        return Some(code_point);
+    }
+
+    if rest.get(1) != Some(&('u' as u16)) {
+        return None;
+    }
+
+    // \u{CodePoint}
+    if rest.get(2) == Some(&('{' as u16)) {
+        let length = rest[3..].iter().position(|&unit| unit == '}' as u16)?;
+        if length == 0 {
+            return None;
+        }
+        let code_point = parse_hex(&rest[3..3 + length]).filter(|&cp| cp <= 0x10FFFF)?;
+        *pos += 4 + length;
+        return Some(code_point);
+    }
+
+    // \uHex4Digits, or a surrogate pair written as \uLead\uTrail.
+    let lead = parse_hex(rest.get(2..6)?)?;
+    *pos += 6;
+    if (0xD800..0xDC00).contains(&lead) && rest.get(6..8) == Some(&['\\' as u16, 'u' as u16][..]) {
+        if let Some(trail) = rest
+            .get(8..12)
+            .and_then(parse_hex)
+            .filter(|trail| (0xDC00..0xE000).contains(trail))
+        {
+            *pos += 6;
+            return Some(0x10000 + ((lead - 0xD800) << 10) + (trail - 0xDC00));
+        }
+    }
+    Some(lead)
+}
+
+/// Whether `name`, the UTF-16 source text of a group name between `<` and
+/// `>`, is a valid RegExpIdentifierName. Characters may be written as
+/// `\uXXXX` or `\u{X}` escapes, which must still be identifier
+/// characters.
+pub fn is_valid_group_name_utf16(name: &[u16]) -> bool {
+    if name.is_empty() {
+        return false;
+    }
+    let mut pos = 0;
+    while pos < name.len() {
+        let is_start = pos == 0;
+        let valid = match next_code_point(name, &mut pos) {
+            Some(code_point) if is_start => is_identifier_start(code_point),
+            Some(code_point) => is_identifier_part(code_point),
+            None => false,
+        };
+        if !valid {
+            return false;
+        }
+    }
+    true
+}
+
+#[test]
+fn test_identifier_chars() {
+    let chars = |s: &str| s.chars().map(|c| c as u32).collect::<Vec<_>>();
+    for c in chars("aZ$_\u{540D}\u{2118}\u{1D400}") {
+        assert!(is_identifier_start(c), "U+{:04X}", c);
+        assert!(is_identifier_part(c), "U+{:04X}", c);
+    }
+    // DIGIT ONE, ZWNJ, ZWJ, COMBINING GRAVE ACCENT, MIDDLE DOT
+    for c in chars("1\u{200C}\u{200D}\u{300}\u{B7}") {
+        assert!(!is_identifier_start(c), "U+{:04X}", c);
+        assert!(is_identifier_part(c), "U+{:04X}", c);
+    }
+    for c in chars("-\\ \u{A0}\u{2E2F}\u{1F600}") {
+        assert!(!is_identifier_start(c), "U+{:04X}", c);
+        assert!(!is_identifier_part(c), "U+{:04X}", c);
+    }
+    assert!(!is_identifier_part(0xD800));
+    assert!(!is_identifier_part(0x110000));
+}
+
+#[test]
+fn test_is_valid_group_name_utf16() {
+    let valid = |name: &str| is_valid_group_name_utf16(&name.encode_utf16().collect::<Vec<_>>());
+    assert!(valid("a"));
+    assert!(valid("$a_1"));
+    assert!(valid("\u{540D}\u{524D}"));
+    assert!(valid("a\u{200C}b\u{200D}"));
+    assert!(valid("\u{1D400}"));
+    assert!(valid("\\u0061b"));
+    assert!(valid("a\\u{62}"));
+    assert!(valid("\\u{0000000000000061}"));
+    assert!(valid("\\uD835\\uDC00"));
+    assert!(valid("\\u{1D400}"));
+
+    assert!(!valid(""));
+    assert!(!valid("1a"));
+    assert!(!valid("\u{200C}"));
+    assert!(!valid("a-b"));
+    assert!(!valid("a\\"));
+    assert!(!valid("\\x61"));
+    assert!(!valid("\\u61"));
+    assert!(!valid("\\u{}"));
+    assert!(!valid("\\u{61"));
+    assert!(!valid("\\u{110000}"));
+    assert!(!valid("\\u{100000000000061}"));
+    assert!(!valid("\\uD835"));
+    assert!(!valid("\\uD835\\u0061"));
+    assert!(valid("a\\u{30}"));
+    assert!(!valid("\\u{30}"));
+
+    // Lone surrogates are not identifier characters.
+    assert!(!is_valid_group_name_utf16(&[0xD835]));
+    assert!(!is_valid_group_name_utf16(&['a' as u16, 0xDC00]));
+}
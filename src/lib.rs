@@ -2,9 +2,53 @@
 // For details, please see the LICENSE file.
 
 mod blob_provider;
+mod canonicalize;
+mod catalog;
+mod class_compiler;
+mod class_escape;
+mod class_set;
+mod code_point_props;
+mod data_info;
+mod emoji_sequences;
+mod error;
 mod ffi;
+mod identifier;
 mod property;
+mod property_escape;
+mod property_id;
+mod script_extensions;
+mod string_matcher;
+mod string_set;
+mod suggest;
+mod tables;
 mod uniset;
 
-pub use property::get_unicode_set;
+pub use canonicalize::{
+    canonicalize, canonicalize_legacy, canonicalize_unicode, case_classes, case_close,
+    CanonicalizeMode, CaseClass,
+};
+pub use catalog::{properties, PropertyInfo, PropertyKind, PropertyValueInfo};
+pub use class_compiler::compile_class;
+pub use class_escape::{class_escape_set, ClassEscape, RegExpFlags};
+pub use class_set::ClassSetExpression;
+pub use code_point_props::{general_category_names, general_category_of, script_names, script_of};
+pub use data_info::{crate_version, data_checksum, data_keys, unicode_version};
+pub use error::{
+    ClassError, ClassErrorKind, ClassSetError, PropertyError, PropertyEscapeError,
+    PropertyEscapeErrorKind,
+};
+pub use identifier::{is_identifier_part, is_identifier_start, is_valid_group_name_utf16};
+pub use property::{
+    get_property_set, get_property_unicode_set, get_unicode_set, resolve_property,
+    try_get_string_set_with_mode, try_get_unicode_set, try_get_unicode_set_with_mode,
+};
+pub use property_escape::parse_property_escape;
+pub use property_id::PropertyId;
+pub use script_extensions::{script_extensions_of, ScriptExtensions};
+pub use string_matcher::{StringSetMatcher, StringTrieNode};
+pub use string_set::ICU4XStringSet;
+pub use suggest::suggest_property_names;
+pub use tables::MatchingMode;
 pub use uniset::ICU4XUniset;
+
+pub use icu_uniset::enum_props::{GeneralCategory, Script};
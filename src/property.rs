@@ -1,7 +1,14 @@
 // This file is licensed under the same terms as ICU4X.
 // For details, please see the LICENSE file.
 
-use crate::{blob_provider, ICU4XUniset};
+use crate::tables::{
+    get_binary_property, get_enumerated_property, get_general_category, get_script,
+    get_string_property, BinaryProperty, EnumeratedProperty, MatchingMode, StringProperty,
+};
+use crate::{
+    blob_provider, emoji_sequences, script_extensions, ICU4XStringSet, ICU4XUniset, PropertyError,
+    PropertyId,
+};
 use icu_uniset::enum_props::{GeneralCategory, Script};
 use icu_uniset::props::*;
 use icu_uniset::{UnicodeSet, UnicodeSetBuilder};
@@ -19,56 +26,168 @@ use icu_uniset::{UnicodeSet, UnicodeSetBuilder};
 /// case, whitespace, dashes, and underscores are not ignored, and the
 /// Is prefix is not supported.
 pub fn get_unicode_set(prop_name: &str, prop_value: Option<&str>) -> Option<ICU4XUniset> {
-    match prop_value {
-        Some(value) => get_unicode_set_by_name_and_value(prop_name, value),
-        None => get_unicode_set_by_name(prop_name),
+    try_get_unicode_set(prop_name, prop_value).ok()
+}
+
+/// Like [`get_unicode_set`], but reports why the lookup failed.
+pub fn try_get_unicode_set(
+    prop_name: &str,
+    prop_value: Option<&str>,
+) -> Result<ICU4XUniset, PropertyError> {
+    try_get_unicode_set_with_mode(prop_name, prop_value, MatchingMode::Ecma262)
+}
+
+/// Like [`try_get_unicode_set`], but matches names using the given
+/// `mode`. With [`MatchingMode::Uax44Loose`], a lone Script value like
+/// `Greek` is also accepted, as in
+/// [UTS18](https://unicode.org/reports/tr18/#Categories).
+pub fn try_get_unicode_set_with_mode(
+    prop_name: &str,
+    prop_value: Option<&str>,
+    mode: MatchingMode,
+) -> Result<ICU4XUniset, PropertyError> {
+    get_code_point_set(resolve(prop_name, prop_value, mode, false)?)
+}
+
+/// Like [`try_get_unicode_set_with_mode`], but for RegExps with the `v`
+/// flag, where lone property names also include the
+/// [properties of strings](
+/// https://tc39.es/ecma262/multipage/text-processing.html#table-binary-unicode-properties-of-strings)
+/// like `RGI_Emoji`.
+pub fn try_get_string_set_with_mode(
+    prop_name: &str,
+    prop_value: Option<&str>,
+    mode: MatchingMode,
+) -> Result<ICU4XStringSet, PropertyError> {
+    get_set(resolve(prop_name, prop_value, mode, true)?)
+}
+
+/// Resolves a property name and optional value to a [`PropertyId`],
+/// which can be stored and later turned into a set with
+/// [`get_property_set`] or [`get_property_unicode_set`]. If `v_mode`
+/// is set, the properties of strings are also resolved.
+pub fn resolve_property(
+    prop_name: &str,
+    prop_value: Option<&str>,
+    mode: MatchingMode,
+    v_mode: bool,
+) -> Result<PropertyId, PropertyError> {
+    resolve(prop_name, prop_value, mode, v_mode).map(PropertyId::new)
+}
+
+/// Returns the set of a property resolved by [`resolve_property`].
+pub fn get_property_set(id: PropertyId) -> Result<ICU4XStringSet, PropertyError> {
+    get_set(id.property().ok_or(PropertyError::UnknownName)?)
+}
+
+/// Like [`get_property_set`], but fails with
+/// [`PropertyError::UnknownName`] for the properties of strings.
+pub fn get_property_unicode_set(id: PropertyId) -> Result<ICU4XUniset, PropertyError> {
+    get_code_point_set(id.property().ok_or(PropertyError::UnknownName)?)
+}
+
+/// A property, or a property and value, resolved from its names.
+#[derive(Clone, Copy)]
+pub(crate) enum Property {
+    Binary(BinaryProperty),
+    GeneralCategory(GeneralCategory),
+    Script(Script),
+    ScriptExtensions(Script),
+    Strings(StringProperty),
+}
+
+fn resolve(
+    prop_name: &str,
+    prop_value: Option<&str>,
+    mode: MatchingMode,
+    v_mode: bool,
+) -> Result<Property, PropertyError> {
+    let string_property = get_string_property(prop_name, mode).filter(|_| v_mode);
+    match (prop_value, string_property) {
+        (Some(_), Some(_)) => Err(PropertyError::ValueNotAllowed),
+        (Some(value), None) => resolve_by_name_and_value(prop_name, value, mode),
+        (None, Some(prop)) => Ok(Property::Strings(prop)),
+        (None, None) => resolve_by_name(prop_name, mode),
     }
 }
 
 //  UnicodePropertyValueExpression :: UnicodePropertyName = UnicodePropertyValue
-fn get_unicode_set_by_name_and_value(prop_name: &str, prop_value: &str) -> Option<ICU4XUniset> {
-    let provider = blob_provider::get_static_provider();
-
+fn resolve_by_name_and_value(
+    prop_name: &str,
+    prop_value: &str,
+    mode: MatchingMode,
+) -> Result<Property, PropertyError> {
     // Steps 1-3
-    let prop = get_enumerated_property(prop_name)?;
+    let prop = match get_enumerated_property(prop_name, mode) {
+        Some(prop) => prop,
+        None if get_binary_property(prop_name, mode).is_some()
+            || get_general_category(prop_name, mode).is_some() =>
+        {
+            return Err(PropertyError::ValueNotAllowed)
+        }
+        None => return Err(PropertyError::UnknownName),
+    };
 
     // Steps 4-5
-    let set = match prop {
+    match prop {
         EnumeratedProperty::GeneralCategory => {
-            let category = get_general_category(prop_value)?;
-            get_general_category_val_set(provider, category)
+            get_general_category(prop_value, mode).map(Property::GeneralCategory)
         }
-
-        EnumeratedProperty::Script => {
-            let script = get_script(prop_value)?;
-            get_script_val_set(provider, script)
+        EnumeratedProperty::Script => get_script(prop_value, mode).map(Property::Script),
+        EnumeratedProperty::ScriptExtensions => {
+            get_script(prop_value, mode).map(Property::ScriptExtensions)
         }
+    }
+    .ok_or(PropertyError::UnknownValue)
+}
+
+// UnicodePropertyValueExpression :: LoneUnicodePropertyNameOrValue
+fn resolve_by_name(prop_name: &str, mode: MatchingMode) -> Result<Property, PropertyError> {
+    // Steps 1-2.
+    if let Some(general_category) = get_general_category(prop_name, mode) {
+        return Ok(Property::GeneralCategory(general_category));
+    }
 
-        EnumeratedProperty::ScriptExtension => {
-            todo!("Script_Extensions")
+    // Step 3.
+    match get_binary_property(prop_name, mode) {
+        Some(prop) => Ok(Property::Binary(prop)),
+        None if get_enumerated_property(prop_name, mode).is_some() => {
+            Err(PropertyError::ValueRequired)
         }
+        None if mode == MatchingMode::Uax44Loose => get_script(prop_name, mode)
+            .map(Property::Script)
+            .ok_or(PropertyError::UnknownName),
+        None => Err(PropertyError::UnknownName),
     }
-    .expect("Static data should be available for all properties");
+}
 
-    // Step 6
-    Some(ICU4XUniset(set))
+fn get_set(prop: Property) -> Result<ICU4XStringSet, PropertyError> {
+    match prop {
+        Property::Strings(prop) => Ok(emoji_sequences::get_string_property_set(prop)),
+        prop => get_code_point_set(prop).map(Into::into),
+    }
 }
 
-// UnicodePropertyValueExpression :: LoneUnicodePropertyNameOrValue
-fn get_unicode_set_by_name(prop_name: &str) -> Option<ICU4XUniset> {
+pub(crate) fn get_code_point_set(prop: Property) -> Result<ICU4XUniset, PropertyError> {
     let provider = blob_provider::get_static_provider();
-
-    // Steps 1-2.
-    if let Some(general_category) = get_general_category(prop_name) {
-        let set = get_general_category_val_set(provider, general_category)
-            .expect("Static data should be available for all properties");
-        return Some(ICU4XUniset(set));
+    let set = match prop {
+        Property::Binary(prop) => return get_binary_property_set(prop),
+        Property::GeneralCategory(category) => get_general_category_val_set(provider, category),
+        Property::Script(script) => get_script_val_set(provider, script),
+        Property::ScriptExtensions(script) => {
+            return script_extensions::get_script_extensions_val_set(script)
+        }
+        // The properties of strings are only resolved in v-mode.
+        Property::Strings(_) => return Err(PropertyError::UnknownName),
     }
+    .map_err(|_| PropertyError::DataUnavailable)?;
 
-    // Step 3.
-    let prop = get_binary_property(prop_name)?;
+    Ok(ICU4XUniset(set))
+}
+
+fn get_binary_property_set(prop: BinaryProperty) -> Result<ICU4XUniset, PropertyError> {
+    let provider = blob_provider::get_static_provider();
 
-    // Steps 4-5.
     use BinaryProperty as BP;
     let set = match prop {
         BP::Alphabetic => get_alphabetic_property(provider),
@@ -125,376 +244,21 @@ fn get_unicode_set_by_name(prop_name: &str) -> Option<ICU4XUniset> {
         BP::Ascii => {
             let mut builder = UnicodeSetBuilder::new();
             builder.add_range(&('\u{0}'..='\u{7f}'));
-            return Some(builder.build().into());
+            return Ok(builder.build().into());
         }
-        BP::Any => return Some(UnicodeSet::all().into()),
+        BP::Any => return Ok(UnicodeSet::all().into()),
         BP::Assigned => {
             let mut builder = UnicodeSetBuilder::new();
             let unassigned = get_general_category_val_set(provider, GeneralCategory::Unassigned)
-                .expect("Static data should include Gc=Unassigned");
+                .map_err(|_| PropertyError::DataUnavailable)?;
             builder.add_set(&ICU4XUniset(unassigned).get());
             builder.complement();
-            return Some(builder.build().into());
+            return Ok(builder.build().into());
         }
     }
-    .expect("Static data should be available for all properties");
-
-    Some(ICU4XUniset(set))
-}
-
-// Table 69: Non-binary Unicode property aliases and their canonical property names
-// https://tc39.es/ecma262/multipage/text-processing.html#table-nonbinary-unicode-properties
-enum EnumeratedProperty {
-    GeneralCategory,
-    Script,
-    ScriptExtension,
-}
-
-fn get_enumerated_property(prop_name: &str) -> Option<EnumeratedProperty> {
-    Some(match prop_name {
-        "General_Category" | "gc" => EnumeratedProperty::GeneralCategory,
-        "Script" | "sc" => EnumeratedProperty::Script,
-        "Script_Extensions" | "scx" => EnumeratedProperty::ScriptExtension,
-
-        _ => return None,
-    })
-}
-
-// Table 70: Binary Unicode property aliases and their canonical property names
-// https://tc39.es/ecma262/multipage/text-processing.html#table-binary-unicode-properties
-enum BinaryProperty {
-    Alphabetic,
-    AsciiHexDigit,
-    BidiControl,
-    BidiMirrored,
-    CaseIgnorable,
-    Cased,
-    ChangesWhenCasefolded,
-    ChangesWhenCasemapped,
-    ChangesWhenLowercased,
-    ChangesWhenNfkcCasefolded,
-    ChangesWhenTitlecased,
-    ChangesWhenUppercased,
-    Dash,
-    DefaultIgnorableCodePoint,
-    Deprecated,
-    Diacritic,
-    Emoji,
-    EmojiComponent,
-    EmojiModifierBase,
-    EmojiModifier,
-    EmojiPresentation,
-    ExtendedPictographic,
-    Extender,
-    GraphemeBase,
-    GraphemeExtend,
-    HexDigit,
-    IdsBinaryOperator,
-    IdsTrinaryOperator,
-    IdContinue,
-    IdStart,
-    Ideographic,
-    JoinControl,
-    LogicalOrderException,
-    Lowercase,
-    Math,
-    NoncharacterCodePoint,
-    PatternSyntax,
-    PatternWhiteSpace,
-    QuotationMark,
-    Radical,
-    RegionalIndicator,
-    SentenceTerminal,
-    SoftDotted,
-    TerminalPunctuation,
-    UnifiedIdeograph,
-    Uppercase,
-    VariationSelector,
-    WhiteSpace,
-    XidContinue,
-    XidStart,
-
-    // Special cases: See https://unicode.org/reports/tr18/#General_Category_Property
-    Ascii,
-    Any,
-    Assigned,
-}
+    .map_err(|_| PropertyError::DataUnavailable)?;
 
-fn get_binary_property(prop_name: &str) -> Option<BinaryProperty> {
-    Some(match prop_name {
-        "Alphabetic" | "Alpha" => BinaryProperty::Alphabetic,
-        "ASCII_Hex_Digit" | "AHex" => BinaryProperty::AsciiHexDigit,
-        "Bidi_Control" | "Bidi_C" => BinaryProperty::BidiControl,
-        "Bidi_Mirrored" | "Bidi_M" => BinaryProperty::BidiMirrored,
-        "Case_Ignorable" | "CI" => BinaryProperty::CaseIgnorable,
-        "Cased" => BinaryProperty::Cased,
-        "Changes_When_Casefolded" | "CWCF" => BinaryProperty::ChangesWhenCasefolded,
-        "Changes_When_Casemapped" | "CWCM" => BinaryProperty::ChangesWhenCasemapped,
-        "Changes_When_Lowercased" | "CWL" => BinaryProperty::ChangesWhenLowercased,
-        "Changes_When_NFKC_Casefolded" | "CWKCF" => BinaryProperty::ChangesWhenNfkcCasefolded,
-        "Changes_When_Titlecased" | "CWT" => BinaryProperty::ChangesWhenTitlecased,
-        "Changes_When_Uppercased" | "CWU" => BinaryProperty::ChangesWhenUppercased,
-        "Dash" => BinaryProperty::Dash,
-        "Default_Ignorable_Code_Point" | "DI" => BinaryProperty::DefaultIgnorableCodePoint,
-        "Deprecated" | "Dep" => BinaryProperty::Deprecated,
-        "Diacritic" | "Dia" => BinaryProperty::Diacritic,
-        "Emoji" => BinaryProperty::Emoji,
-        "Emoji_Component" | "EComp" => BinaryProperty::EmojiComponent,
-        "Emoji_Modifier_Base" | "EBase" => BinaryProperty::EmojiModifierBase,
-        "Emoji_Modifier" | "EMod" => BinaryProperty::EmojiModifier,
-        "Emoji_Presentation" | "EPres" => BinaryProperty::EmojiPresentation,
-        "Extended_Pictographic" | "ExtPict" => BinaryProperty::ExtendedPictographic,
-        "Extender" | "Ext" => BinaryProperty::Extender,
-        "Grapheme_Base" | "Gr_Base" => BinaryProperty::GraphemeBase,
-        "Grapheme_Extend" | "Gr_Ext" => BinaryProperty::GraphemeExtend,
-        "Hex_Digit" | "Hex" => BinaryProperty::HexDigit,
-        "IDS_Binary_Operator" | "IDSB" => BinaryProperty::IdsBinaryOperator,
-        "IDS_Trinary_Operator" | "IDST" => BinaryProperty::IdsTrinaryOperator,
-        "Id_Continue" | "IDC" => BinaryProperty::IdContinue,
-        "Id_Start" | "IDS" => BinaryProperty::IdStart,
-        "Ideographic" | "Ideo" => BinaryProperty::Ideographic,
-        "Join_Control" | "JoinC" => BinaryProperty::JoinControl,
-        "Logical_Order_Exception" | "LOE" => BinaryProperty::LogicalOrderException,
-        "Lowercase" | "Lower" => BinaryProperty::Lowercase,
-        "Math" => BinaryProperty::Math,
-        "Noncharacter_Code_Point" | "NChar" => BinaryProperty::NoncharacterCodePoint,
-        "Pattern_Syntax" | "Pat_Syn" => BinaryProperty::PatternSyntax,
-        "Pattern_White_Space" | "Pat_WS" => BinaryProperty::PatternWhiteSpace,
-        "Quotation_Mark" | "QMark" => BinaryProperty::QuotationMark,
-        "Radical" => BinaryProperty::Radical,
-        "Regional_Indicator" | "RI" => BinaryProperty::RegionalIndicator,
-        "SentenceTerminal" | "STerm" => BinaryProperty::SentenceTerminal,
-        "Soft_Dotted" | "SD" => BinaryProperty::SoftDotted,
-        "Terminal_Punctuation" | "Term" => BinaryProperty::TerminalPunctuation,
-        "Unified_Ideograph" | "UIdeo" => BinaryProperty::UnifiedIdeograph,
-        "Uppercase" | "Upper" => BinaryProperty::Uppercase,
-        "Variation_Selector" | "VS" => BinaryProperty::VariationSelector,
-        "White_Space" | "space" => BinaryProperty::WhiteSpace,
-        "Xid_Continue" | "XIDC" => BinaryProperty::XidContinue,
-        "Xid_Start" | "XIDS" => BinaryProperty::XidStart,
-
-        "ASCII" => BinaryProperty::Ascii,
-        "Any" => BinaryProperty::Any,
-        "Assigned" => BinaryProperty::Assigned,
-
-        _ => return None,
-    })
-}
-
-// Table 71: Value aliases and canonical values for the Unicode property General_Category
-// https://tc39.es/ecma262/multipage/text-processing.html#table-unicode-general-category-values
-fn get_general_category(gc_name: &str) -> Option<GeneralCategory> {
-    Some(match gc_name {
-        "Cased_Letter" | "LC" => GeneralCategory::CasedLetter,
-        "Close_Punctuation" | "Pe" => GeneralCategory::ClosePunctuation,
-        "Connector_Punctuation" | "Pc" => GeneralCategory::ConnectorPunctuation,
-        "Control" | "Cc" | "cntrl" => GeneralCategory::Control,
-        "Currency_Symbol" | "Sc" => GeneralCategory::CurrencySymbol,
-        "Dash_Punctuation" | "Pd" => GeneralCategory::DashPunctuation,
-        "Decimal_Number" | "Nd" | "digit" => GeneralCategory::Digit,
-        "Enclosing_Mark" | "Me" => GeneralCategory::EnclosingMark,
-        "Final_Punctuation" | "Pf" => GeneralCategory::FinalPunctuation,
-        "Format" | "Cf" => GeneralCategory::Format,
-        "Initial_Punctuation" | "Pi" => GeneralCategory::InitialPunctuation,
-        "Letter" | "L" => GeneralCategory::Letter,
-        "Letter_Number" | "Nl" => GeneralCategory::LetterNumber,
-        "Line_Separator" | "Zl" => GeneralCategory::LineSeparator,
-        "Lowercase_Letter" | "Ll" => GeneralCategory::LowercaseLetter,
-        "Mark" | "M" | "Combining_Mark" => GeneralCategory::Mark,
-        "Math_Symbol" | "Sm" => GeneralCategory::MathSymbol,
-        "Modifier_Letter" | "Lm" => GeneralCategory::ModifierLetter,
-        "Modifier_Symbol" | "Sk" => GeneralCategory::ModifierSymbol,
-        "Nonspacing_Mark" | "Mn" => GeneralCategory::NonspacingMark,
-        "Number" | "N" => GeneralCategory::Number,
-        "Open_Punctuation" | "Ps" => GeneralCategory::OpenPunctuation,
-        "Other" | "C" => GeneralCategory::Other,
-        "Other_Letter" | "Lo" => GeneralCategory::OtherLetter,
-        "Other_Number" | "No" => GeneralCategory::OtherNumber,
-        "Other_Punctuation" | "Po" => GeneralCategory::OtherPunctuation,
-        "Other_Symbol" | "So" => GeneralCategory::OtherSymbol,
-        "Paragraph_Separator" | "Zp" => GeneralCategory::ParagraphSeparator,
-        "Private_Use" | "Co" => GeneralCategory::PrivateUse,
-        "Punctuation" | "P" | "punct" => GeneralCategory::Punctuation,
-        "Separator" | "Z" => GeneralCategory::Separator,
-        "Space_Separator" | "Zs" => GeneralCategory::SpaceSeparator,
-        "Spacing_Mark" | "Mc" => GeneralCategory::SpacingMark,
-        "Surrogate" | "Cs" => GeneralCategory::Surrogate,
-        "Symbol" | "S" => GeneralCategory::Symbol,
-        "Titlecase_Letter" | "Lt" => GeneralCategory::TitlecaseLetter,
-        "Unassigned" | "Cn" => GeneralCategory::Unassigned,
-        "Uppercase_Letter" | "Lu" => GeneralCategory::UppercaseLetter,
-        _ => return None,
-    })
-}
-
-// Table 72: Value aliases and canonical values for the Unicode properties Script and Script_Extensions.
-// https://tc39.es/ecma262/multipage/text-processing.html#table-unicode-script-values
-fn get_script(script_name: &str) -> Option<Script> {
-    Some(match script_name {
-        "Adlam" | "Adlm" => Script::Adlam,
-        "Ahom" => Script::Ahom,
-        "Anatolian_Hieroglyphs" | "Hluw" => Script::AnatolianHieroglyphs,
-        "Arabic" | "Arab" => Script::Arabic,
-        "Armenian" | "Armn" => Script::Armenian,
-        "Avestan" | "Avst" => Script::Avestan,
-        "Balinese" | "Bali" => Script::Balinese,
-        "Bamum" | "Bamu" => Script::Bamum,
-        "Bassa_Vah" | "Bass" => Script::BassaVah,
-        "Batak" | "Batk" => Script::Batak,
-        "Bengali" | "Beng" => Script::Bengali,
-        "Bhaiksuki" | "Bhks" => Script::Bhaiksuki,
-        "Bopomofo" | "Bopo" => Script::Bopomofo,
-        "Brahmi" | "Brah" => Script::Brahmi,
-        "Braille" | "Brai" => Script::Braille,
-        "Buginese" | "Bugi" => Script::Buginese,
-        "Buhid" | "Buhd" => Script::Buhid,
-        "Canadian_Aboriginal" | "Cans" => Script::CanadianAboriginal,
-        "Carian" | "Cari" => Script::Carian,
-        "Caucasian_Albanian" | "Aghb" => Script::CaucasianAlbanian,
-        "Chakma" | "Cakm" => Script::Chakma,
-        "Cham" => Script::Cham,
-        "Cherokee" | "Cher" => Script::Cherokee,
-        "Chorasmian" | "Chrs" => Script::Chorasmian,
-        "Common" | "Zyyy" => Script::Common,
-        "Coptic" | "Copt" => Script::Coptic,
-        "Cuneiform" | "Xsux" => Script::Cuneiform,
-        "Cypriot" | "Cprt" => Script::Cypriot,
-        "Cypro_Minoan" | "Cpmn" => Script::CyproMinoan,
-        "Cyrillic" | "Cyrl" => Script::Cyrillic,
-        "Deseret" | "Dsrt" => Script::Deseret,
-        "Devanagari" | "Deva" => Script::Devanagari,
-        "Dives_Akuru" | "Diak" => Script::DivesAkuru,
-        "Dogra" | "Dogr" => Script::Dogra,
-        "Duployan" | "Dupl" => Script::Duployan,
-        "Egyptian_Hieroglyphs" | "Egyp" => Script::EgyptianHieroglyphs,
-        "Elbasan" | "Elba" => Script::Elbasan,
-        "Elymaic" | "Elym" => Script::Elymaic,
-        "Ethiopic" | "Ethi" => Script::Ethiopic,
-        "Georgian" | "Geor" => Script::Georgian,
-        "Glagolitic" | "Glag" => Script::Glagolitic,
-        "Gothic" | "Goth" => Script::Gothic,
-        "Grantha" | "Gran" => Script::Grantha,
-        "Greek" | "Grek" => Script::Greek,
-        "Gujarati" | "Gujr" => Script::Gujarati,
-        "Gunjala_Gondi" | "Gong" => Script::GunjalaGondi,
-        "Gurmukhi" | "Guru" => Script::Gurmukhi,
-        "Han" | "Hani" => Script::Han,
-        "Hangul" | "Hang" => Script::Hangul,
-        "Hanifi_Rohingya" | "Rohg" => Script::HanifiRohingya,
-        "Hanunoo" | "Hano" => Script::Hanunoo,
-        "Hatran" | "Hatr" => Script::Hatran,
-        "Hebrew" | "Hebr" => Script::Hebrew,
-        "Hiragana" | "Hira" => Script::Hiragana,
-        "Imperial_Aramaic" | "Armi" => Script::ImperialAramaic,
-        "Inherited" | "Zinh" => Script::Inherited,
-        "Inscriptional_Pahlavi" | "Phli" => Script::InscriptionalPahlavi,
-        "Inscriptional_Parthian" | "Prti" => Script::InscriptionalParthian,
-        "Javanese" | "Java" => Script::Javanese,
-        "Kaithi" | "Kthi" => Script::Kaithi,
-        "Kannada" | "Knda" => Script::Kannada,
-        "Katakana" | "Kana" => Script::Katakana,
-        "Kayah_Li" | "Kali" => Script::KayahLi,
-        "Kharoshthi" | "Khar" => Script::Kharoshthi,
-        "Khitan_Small_Script" | "Kits" => Script::KhitanSmallScript,
-        "Khmer" | "Khmr" => Script::Khmer,
-        "Khojki" | "Khoj" => Script::Khojki,
-        "Khudawadi" | "Sind" => Script::Khudawadi,
-        "Lao" | "Laoo" => Script::Lao,
-        "Latin" | "Latn" => Script::Latin,
-        "Lepcha" | "Lepc" => Script::Lepcha,
-        "Limbu" | "Limb" => Script::Limbu,
-        "Linear_A" | "Lina" => Script::LinearA,
-        "Linear_B" | "Linb" => Script::LinearB,
-        "Lisu" => Script::Lisu,
-        "Lycian" | "Lyci" => Script::Lycian,
-        "Lydian" | "Lydi" => Script::Lydian,
-        "Mahajani" | "Mahj" => Script::Mahajani,
-        "Makasar" | "Maka" => Script::Makasar,
-        "Malayalam" | "Mlym" => Script::Malayalam,
-        "Mandaic" | "Mand" => Script::Mandaic,
-        "Manichaean" | "Mani" => Script::Manichaean,
-        "Marchen" | "Marc" => Script::Marchen,
-        "Masaram_Gondi" | "Gonm" => Script::MasaramGondi,
-        "Medefaidrin" | "Medf" => Script::Medefaidrin,
-        "Meetei_Mayek" | "Mtei" => Script::MeeteiMayek,
-        "Mende_Kikakui" | "Mend" => Script::MendeKikakui,
-        "Meroitic_Cursive" | "Merc" => Script::MeroiticCursive,
-        "Meroitic_Hieroglyphs" | "Mero" => Script::MeroiticHieroglyphs,
-        "Miao" | "Plrd" => Script::Miao,
-        "Modi" => Script::Modi,
-        "Mongolian" | "Mong" => Script::Mongolian,
-        "Mro" | "Mroo" => Script::Mro,
-        "Multani" | "Mult" => Script::Multani,
-        "Myanmar" | "Mymr" => Script::Myanmar,
-        "Nabataean" | "Nbat" => Script::Nabataean,
-        "Nandinagari" | "Nand" => Script::Nandinagari,
-        "New_Tai_Lue" | "Talu" => Script::NewTaiLue,
-        "Newa" => Script::Newa,
-        "Nko" | "Nkoo" => Script::Nko,
-        "Nushu" | "Nshu" => Script::Nushu,
-        "Nyiakeng_Puachue_Hmong" | "Hmnp" => Script::NyiakengPuachueHmong,
-        "Ogham" | "Ogam" => Script::Ogham,
-        "Ol_Chiki" | "Olck" => Script::OlChiki,
-        "Old_Hungarian" | "Hung" => Script::OldHungarian,
-        "Old_Italic" | "Ital" => Script::OldItalic,
-        "Old_North_Arabian" | "Narb" => Script::OldNorthArabian,
-        "Old_Permic" | "Perm" => Script::OldPermic,
-        "Old_Persian" | "Xpeo" => Script::OldPersian,
-        "Old_Sogdian" | "Sogo" => Script::OldSogdian,
-        "Old_South_Arabian" | "Sarb" => Script::OldSouthArabian,
-        "Old_Turkic" | "Orkh" => Script::OldTurkic,
-        "Old_Uyghur" | "Ougr" => Script::OldUyghur,
-        "Oriya" | "Orya" => Script::Oriya,
-        "Osage" | "Osge" => Script::Osage,
-        "Osmanya" | "Osma" => Script::Osmanya,
-        "Pahawh_Hmong" | "Hmng" => Script::PahawhHmong,
-        "Palmyrene" | "Palm" => Script::Palmyrene,
-        "Pau_Cin_Hau" | "Pauc" => Script::PauCinHau,
-        "Phags_Pa" | "Phag" => Script::PhagsPa,
-        "Phoenician" | "Phnx" => Script::Phoenician,
-        "Psalter_Pahlavi" | "Phlp" => Script::PsalterPahlavi,
-        "Rejang" | "Rjng" => Script::Rejang,
-        "Runic" | "Runr" => Script::Runic,
-        "Samaritan" | "Samr" => Script::Samaritan,
-        "Saurashtra" | "Saur" => Script::Saurashtra,
-        "Sharada" | "Shrd" => Script::Sharada,
-        "Shavian" | "Shaw" => Script::Shavian,
-        "Siddham" | "Sidd" => Script::Siddham,
-        "SignWriting" | "Sgnw" => Script::SignWriting,
-        "Sinhala" | "Sinh" => Script::Sinhala,
-        "Sogdian" | "Sogd" => Script::Sogdian,
-        "Sora_Sompeng" | "Sora" => Script::SoraSompeng,
-        "Soyombo" | "Soyo" => Script::Soyombo,
-        "Sundanese" | "Sund" => Script::Sundanese,
-        "Syloti_Nagri" | "Sylo" => Script::SylotiNagri,
-        "Syriac" | "Syrc" => Script::Syriac,
-        "Tagalog" | "Tglg" => Script::Tagalog,
-        "Tagbanwa" | "Tagb" => Script::Tagbanwa,
-        "Tai_Le" | "Tale" => Script::TaiLe,
-        "Tai_Tham" | "Lana" => Script::TaiTham,
-        "Tai_Viet" | "Tavt" => Script::TaiViet,
-        "Takri" | "Takr" => Script::Takri,
-        "Tamil" | "Taml" => Script::Tamil,
-        "Tangsa" | "Tnsa" => Script::Tangsa,
-        "Tangut" | "Tang" => Script::Tangut,
-        "Telugu" | "Telu" => Script::Telugu,
-        "Thaana" | "Thaa" => Script::Thaana,
-        "Thai" => Script::Thai,
-        "Tibetan" | "Tibt" => Script::Tibetan,
-        "Tifinagh" | "Tfng" => Script::Tifinagh,
-        "Tirhuta" | "Tirh" => Script::Tirhuta,
-        "Toto" => Script::Toto,
-        "Ugaritic" | "Ugar" => Script::Ugaritic,
-        "Unknown" | "Zzzz" => Script::Unknown,
-        "Vai" | "Vaii" => Script::Vai,
-        "Vithkuqi" | "Vith" => Script::Vithkuqi,
-        "Wancho" | "Wcho" => Script::Wancho,
-        "Warang_Citi" | "Wara" => Script::WarangCiti,
-        "Yezidi" | "Yezi" => Script::Yezidi,
-        "Yi" | "Yiii" => Script::Yi,
-        "Zanabazar_Square" | "Zanb" => Script::ZanabazarSquare,
-        _ => return None,
-    })
+    Ok(ICU4XUniset(set))
 }
 
 #[test]
@@ -540,3 +304,148 @@ fn test_special() {
         any.get().get_inversion_list()
     );
 }
+
+#[test]
+fn test_script_extensions() {
+    let greek1 = get_unicode_set("Script_Extensions", Some("Greek")).unwrap();
+    let greek2 = get_unicode_set("scx", Some("Grek")).unwrap();
+    assert_eq!(
+        greek1.get().get_inversion_list(),
+        greek2.get().get_inversion_list()
+    );
+    assert!(greek1.get().contains('\u{03B1}')); // U+03B1 GREEK SMALL LETTER ALPHA
+    assert!(greek1.get().contains('\u{0342}')); // U+0342 COMBINING GREEK PERISPOMENI
+
+    // U+3001 IDEOGRAPHIC COMMA is Script=Common, but extends to Hiragana.
+    let hiragana_sc = get_unicode_set("sc", Some("Hira")).unwrap();
+    let hiragana_scx = get_unicode_set("scx", Some("Hira")).unwrap();
+    assert!(!hiragana_sc.get().contains('\u{3001}'));
+    assert!(hiragana_scx.get().contains('\u{3001}'));
+
+    // Code points with explicit extensions are removed from their Script.
+    let common_scx = get_unicode_set("scx", Some("Zyyy")).unwrap();
+    assert!(!common_scx.get().contains('\u{3001}'));
+    assert!(common_scx.get().contains(' '));
+}
+
+#[test]
+fn test_changes_when_casemapped() {
+    let cwcm1 = get_unicode_set("Changes_When_Casemapped", None).unwrap();
+    let cwcm2 = get_unicode_set("CWCM", None).unwrap();
+    assert_eq!(
+        cwcm1.get().get_inversion_list(),
+        cwcm2.get().get_inversion_list()
+    );
+    assert!(cwcm1.get().contains('A'));
+    assert!(!cwcm1.get().contains('1'));
+}
+
+#[test]
+fn test_errors() {
+    let error = |name, value| try_get_unicode_set(name, value).err();
+    assert_eq!(error("Alpha", None), None);
+    assert_eq!(
+        error("Alphabetical", None),
+        Some(PropertyError::UnknownName)
+    );
+    assert_eq!(error("Foo", Some("Bar")), Some(PropertyError::UnknownName));
+    assert_eq!(error("sc", Some("Foo")), Some(PropertyError::UnknownValue));
+    assert_eq!(
+        error("Alpha", Some("Yes")),
+        Some(PropertyError::ValueNotAllowed)
+    );
+    assert_eq!(
+        error("Lu", Some("Yes")),
+        Some(PropertyError::ValueNotAllowed)
+    );
+    assert_eq!(error("Script", None), Some(PropertyError::ValueRequired));
+    assert_eq!(error("scx", None), Some(PropertyError::ValueRequired));
+}
+
+#[test]
+fn test_loose_matching() {
+    let loose = |name, value| try_get_unicode_set_with_mode(name, value, MatchingMode::Uax44Loose);
+    let greek = get_unicode_set("sc", Some("Greek")).unwrap();
+    for set in [
+        loose("greek", None).unwrap(),
+        loose("Script ", Some(" Greek")).unwrap(),
+        loose("IsGreek", None).unwrap(),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            set.get().get_inversion_list(),
+            greek.get().get_inversion_list()
+        );
+    }
+
+    let alpha = get_unicode_set("Alphabetic", None).unwrap();
+    assert_eq!(
+        loose("IsAlphabetic", None)
+            .unwrap()
+            .get()
+            .get_inversion_list(),
+        alpha.get().get_inversion_list()
+    );
+
+    assert!(get_unicode_set("greek", None).is_none());
+    assert_eq!(loose("Grook", None).err(), Some(PropertyError::UnknownName));
+}
+
+#[test]
+fn test_properties_of_strings() {
+    let v_mode = |name| try_get_string_set_with_mode(name, None, MatchingMode::Ecma262);
+    assert_eq!(
+        try_get_unicode_set("RGI_Emoji", None).err(),
+        Some(PropertyError::UnknownName)
+    );
+    assert_eq!(
+        try_get_string_set_with_mode("RGI_Emoji", Some("Yes"), MatchingMode::Ecma262).err(),
+        Some(PropertyError::ValueNotAllowed)
+    );
+
+    let keycaps = v_mode("Emoji_Keycap_Sequence").unwrap();
+    assert_eq!(keycaps.code_points().get().get_range_count(), 0);
+    assert_eq!(keycaps.strings().len(), 12);
+    let keycap_hash: Vec<u16> = "#\u{FE0F}\u{20E3}".encode_utf16().collect();
+    assert!(keycaps.strings().contains(&keycap_hash));
+
+    // U+231A WATCH has Emoji_Presentation; U+00A9 COPYRIGHT SIGN needs
+    // U+FE0F VARIATION SELECTOR-16.
+    let basic = v_mode("Basic_Emoji").unwrap();
+    assert!(basic.code_points().get().contains('\u{231A}'));
+    assert!(!basic.code_points().get().contains('\u{00A9}'));
+    let copyright: Vec<u16> = "\u{00A9}\u{FE0F}".encode_utf16().collect();
+    assert!(basic.strings().contains(&copyright));
+
+    // RGI_Emoji is the union of the other properties of strings.
+    let rgi = v_mode("RGI_Emoji").unwrap();
+    let mut string_count = 0;
+    for name in [
+        "Basic_Emoji",
+        "Emoji_Keycap_Sequence",
+        "RGI_Emoji_Flag_Sequence",
+        "RGI_Emoji_Modifier_Sequence",
+        "RGI_Emoji_Tag_Sequence",
+        "RGI_Emoji_ZWJ_Sequence",
+    ]
+    .iter()
+    {
+        let set = v_mode(name).unwrap();
+        assert!(set.has_strings(), "{} should contain strings", name);
+        for string in set.strings() {
+            assert!(rgi.strings().contains(string));
+        }
+        string_count += set.strings().len();
+    }
+    assert_eq!(rgi.strings().len(), string_count);
+    assert_eq!(
+        rgi.code_points().get().get_inversion_list(),
+        basic.code_points().get().get_inversion_list()
+    );
+
+    // Other properties are unchanged in v-mode.
+    let greek = try_get_string_set_with_mode("sc", Some("Greek"), MatchingMode::Ecma262).unwrap();
+    assert!(!greek.has_strings());
+    assert!(greek.code_points().get().contains('\u{03B1}'));
+}
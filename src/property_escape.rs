@@ -0,0 +1,176 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! Parsing of the `{Name=Value}` part of a `\p{…}` or `\P{…}` escape,
+//! following the
+//! [UnicodePropertyValueExpression](https://tc39.es/ecma262/multipage/text-processing.html#prod-UnicodePropertyValueExpression)
+//! grammar, directly from the source of a pattern.
+
+use crate::{
+    get_property_set, resolve_property, ICU4XStringSet, MatchingMode, PropertyError,
+    PropertyEscapeError, PropertyEscapeErrorKind,
+};
+use icu_uniset::UnicodeSetBuilder;
+
+/// Whether `unit` is a UnicodePropertyNameCharacter.
+fn is_name_character(unit: u32) -> bool {
+    unit < 0x80 && ((unit as u8).is_ascii_alphabetic() || unit == '_' as u32)
+}
+
+/// Whether `unit` is a UnicodePropertyValueCharacter.
+fn is_value_character(unit: u32) -> bool {
+    is_name_character(unit) || (unit < 0x80 && (unit as u8).is_ascii_digit())
+}
+
+/// Parses and resolves a property escape in `pattern`, a Latin1 or UTF-16
+/// buffer, where `cursor` is the offset just after the `\p` or `\P`.
+/// `negated` is set for `\P`. If `v_mode` is set, the properties of
+/// strings are also resolved.
+///
+/// Returns the set and the number of code units consumed, up to and
+/// including the closing `}`. The offset of an error is relative to the
+/// start of `pattern`.
+pub fn parse_property_escape<T: Copy + Into<u32>>(
+    pattern: &[T],
+    cursor: usize,
+    negated: bool,
+    v_mode: bool,
+) -> Result<(ICU4XStringSet, usize), PropertyEscapeError> {
+    let unit = |offset: usize| -> Option<u32> { pattern.get(offset).map(|&unit| unit.into()) };
+    let error = |kind, offset| PropertyEscapeError { kind, offset };
+    let scan_value = |start: usize| {
+        (start..pattern.len())
+            .find(|&offset| !is_value_character(pattern[offset].into()))
+            .unwrap_or(pattern.len())
+    };
+
+    if unit(cursor) != Some('{' as u32) {
+        return Err(error(PropertyEscapeErrorKind::MissingOpenBrace, cursor));
+    }
+
+    // Either `Name=Value` or a LoneUnicodePropertyNameOrValue.
+    let name_start = cursor + 1;
+    let name_end = scan_value(name_start);
+    let value_range = if unit(name_end) == Some('=' as u32) {
+        if let Some(offset) =
+            (name_start..name_end).find(|&offset| !is_name_character(pattern[offset].into()))
+        {
+            return Err(error(PropertyEscapeErrorKind::InvalidCharacter, offset));
+        }
+        if name_start == name_end {
+            return Err(error(PropertyEscapeErrorKind::InvalidCharacter, name_end));
+        }
+        Some((name_end + 1, scan_value(name_end + 1)))
+    } else {
+        None
+    };
+
+    let (last_start, end) = value_range.unwrap_or((name_start, name_end));
+    match unit(end) {
+        None => return Err(error(PropertyEscapeErrorKind::MissingCloseBrace, end)),
+        Some(_) if last_start == end => {
+            return Err(error(PropertyEscapeErrorKind::InvalidCharacter, end))
+        }
+        Some(c) if c != '}' as u32 => {
+            return Err(error(PropertyEscapeErrorKind::InvalidCharacter, end))
+        }
+        Some(_) => {}
+    }
+
+    // The names are ASCII.
+    let to_string = |(start, end): (usize, usize)| -> String {
+        pattern[start..end]
+            .iter()
+            .map(|&unit| Into::<u32>::into(unit) as u8 as char)
+            .collect()
+    };
+    let name = to_string((name_start, name_end));
+    let value = value_range.map(to_string);
+
+    let set = resolve_property(&name, value.as_deref(), MatchingMode::Ecma262, v_mode)
+        .and_then(get_property_set)
+        .map_err(|e| {
+            let offset = match (e, value_range) {
+                (PropertyError::UnknownValue, Some((value_start, _)))
+                | (PropertyError::ValueNotAllowed, Some((value_start, _))) => value_start,
+                _ => name_start,
+            };
+            error(e.into(), offset)
+        })?;
+
+    let set = if negated {
+        if set.has_strings() {
+            return Err(error(PropertyEscapeErrorKind::NegatedStrings, name_start));
+        }
+        let mut builder = UnicodeSetBuilder::new();
+        builder.add_set(set.code_points().get());
+        builder.complement();
+        builder.build().into()
+    } else {
+        set
+    };
+    Ok((set, end + 1 - cursor))
+}
+
+#[test]
+fn test_parse_property_escape() {
+    use std::convert::TryFrom;
+    use PropertyEscapeErrorKind::*;
+
+    let parse = |pattern: &str, cursor, negated, v_mode| {
+        let utf16: Vec<u16> = pattern.encode_utf16().collect();
+        let result = parse_property_escape(&utf16, cursor, negated, v_mode);
+
+        // Latin1 patterns parse the same way.
+        if let Ok(latin1) = pattern
+            .chars()
+            .map(|c| u8::try_from(c as u32))
+            .collect::<Result<Vec<u8>, _>>()
+        {
+            let latin1_result = parse_property_escape(&latin1, cursor, negated, v_mode);
+            assert_eq!(
+                latin1_result.as_ref().map(|(_, length)| *length),
+                result.as_ref().map(|(_, length)| *length)
+            );
+        }
+        result
+    };
+    let length = |pattern, cursor| parse(pattern, cursor, false, false).unwrap().1;
+    let error = |pattern, v_mode| parse(pattern, 2, false, v_mode).err().unwrap();
+
+    assert_eq!(length("\\p{L}", 2), 3);
+    assert_eq!(length("a\\p{Script=Greek}+", 3), 14);
+    assert_eq!(length("\\p{General_Category=Decimal_Number}", 2), 33);
+    let (set, _) = parse("\\p{Lu}", 2, false, false).unwrap();
+    assert!(set.code_points().get().contains('A'));
+    let (set, _) = parse("\\P{Lu}", 2, true, false).unwrap();
+    assert!(!set.code_points().get().contains('A'));
+    assert!(set.code_points().get().contains('a'));
+
+    let (set, length) = parse("\\p{RGI_Emoji}", 2, false, true).unwrap();
+    assert_eq!(length, 11);
+    assert!(set.has_strings());
+
+    let at = |kind, offset| PropertyEscapeError { kind, offset };
+    assert_eq!(error("\\pL", false), at(MissingOpenBrace, 2));
+    assert_eq!(error("\\p", false), at(MissingOpenBrace, 2));
+    assert_eq!(error("\\p{", false), at(MissingCloseBrace, 3));
+    assert_eq!(error("\\p{Lu", false), at(MissingCloseBrace, 5));
+    assert_eq!(error("\\p{}", false), at(InvalidCharacter, 3));
+    assert_eq!(error("\\p{L u}", false), at(InvalidCharacter, 4));
+    assert_eq!(error("\\p{=Lu}", false), at(InvalidCharacter, 3));
+    assert_eq!(error("\\p{gc=}", false), at(InvalidCharacter, 6));
+    assert_eq!(error("\\p{g1=Lu}", false), at(InvalidCharacter, 4));
+    assert_eq!(error("\\p{gc=Lu=Ll}", false), at(InvalidCharacter, 8));
+    assert_eq!(error("\\p{sc=Gr\u{E9}k}", false), at(InvalidCharacter, 8));
+    assert_eq!(error("\\p{Lu\u{1F600}}", false), at(InvalidCharacter, 5));
+    assert_eq!(error("\\p{Foo}", false), at(UnknownName, 3));
+    assert_eq!(error("\\p{Script=Foo}", false), at(UnknownValue, 10));
+    assert_eq!(error("\\p{Alpha=Yes}", false), at(ValueNotAllowed, 9));
+    assert_eq!(error("\\p{Script}", false), at(ValueRequired, 3));
+    assert_eq!(error("\\p{RGI_Emoji}", false), at(UnknownName, 3));
+    assert_eq!(
+        parse("\\P{RGI_Emoji}", 2, true, true).err().unwrap(),
+        at(NegatedStrings, 3)
+    );
+}
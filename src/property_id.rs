@@ -0,0 +1,156 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+use crate::property::Property;
+use crate::tables::{
+    BinaryProperty, BINARY_PROPERTIES, GENERAL_CATEGORIES, SCRIPTS, SPECIAL_BINARY_PROPERTIES,
+    STRING_PROPERTIES,
+};
+
+const BINARY: u16 = 0;
+const GENERAL_CATEGORY: u16 = 1;
+const SCRIPT: u16 = 2;
+const SCRIPT_EXTENSIONS: u16 = 3;
+const STRINGS: u16 = 4;
+
+/// A property, or a property and value, that has been resolved from its
+/// names by [`resolve_property`](crate::resolve_property).
+///
+/// Its contents are opaque, but stable: they hold the ICU4X values of
+/// General_Category and Script, and the fixed discriminants of the binary
+/// properties, so IDs do not change when properties are added to the data.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PropertyId {
+    kind: u16,
+    index: u16,
+}
+
+impl PropertyId {
+    /// Whether the set of this property may contain strings, so that it
+    /// can only be used with the `v` flag and cannot be negated.
+    pub fn may_contain_strings(self) -> bool {
+        self.kind == STRINGS
+    }
+
+    pub(crate) fn new(prop: Property) -> Self {
+        let (kind, index) = match prop {
+            Property::Binary(prop) => (BINARY, prop as u16),
+            Property::GeneralCategory(gc) => (GENERAL_CATEGORY, gc as u16),
+            Property::Script(script) => (SCRIPT, script.0),
+            Property::ScriptExtensions(script) => (SCRIPT_EXTENSIONS, script.0),
+            Property::Strings(prop) => (STRINGS, prop as u16),
+        };
+        PropertyId { kind, index }
+    }
+
+    /// Returns the property this ID stands for, or `None` if it was not
+    /// returned by `resolve_property`.
+    pub(crate) fn property(self) -> Option<Property> {
+        let index = self.index;
+        let script = || {
+            SCRIPTS
+                .iter()
+                .map(|&(_, _, script)| script)
+                .find(|script| script.0 == index)
+        };
+        match self.kind {
+            BINARY => binary_properties()
+                .find(|&prop| prop as u16 == index)
+                .map(Property::Binary),
+            GENERAL_CATEGORY => GENERAL_CATEGORIES
+                .iter()
+                .map(|&(_, _, gc)| gc)
+                .find(|&gc| gc as u16 == index)
+                .map(Property::GeneralCategory),
+            SCRIPT => script().map(Property::Script),
+            SCRIPT_EXTENSIONS => script().map(Property::ScriptExtensions),
+            STRINGS => STRING_PROPERTIES
+                .iter()
+                .map(|&(_, prop)| prop)
+                .find(|&prop| prop as u16 == index)
+                .map(Property::Strings),
+            _ => None,
+        }
+    }
+}
+
+fn binary_properties() -> impl Iterator<Item = BinaryProperty> {
+    BINARY_PROPERTIES
+        .iter()
+        .map(|&(_, _, prop)| prop)
+        .chain(SPECIAL_BINARY_PROPERTIES.iter().map(|&(_, prop)| prop))
+}
+
+#[test]
+fn test_property_id() {
+    use crate::{
+        get_property_set, get_property_unicode_set, get_unicode_set, resolve_property,
+        GeneralCategory, MatchingMode, PropertyError,
+    };
+
+    let resolve = |name, value| resolve_property(name, value, MatchingMode::Ecma262, false);
+    let cases = [
+        ("Alphabetic", None),
+        ("ASCII", None),
+        ("Lu", None),
+        ("gc", Some("Nd")),
+        ("sc", Some("Greek")),
+        ("scx", Some("Hira")),
+    ];
+    for &(name, value) in cases.iter() {
+        let id = resolve(name, value).unwrap();
+        assert_eq!(id, PropertyId::new(id.property().unwrap()));
+        assert!(!id.may_contain_strings());
+        assert_eq!(
+            get_property_unicode_set(id)
+                .unwrap()
+                .get()
+                .get_inversion_list(),
+            get_unicode_set(name, value)
+                .unwrap()
+                .get()
+                .get_inversion_list()
+        );
+    }
+    assert_eq!(resolve("Alpha", None), resolve("Alphabetic", None));
+    assert_ne!(resolve("sc", Some("Greek")), resolve("scx", Some("Greek")));
+    assert_eq!(resolve("Foo", None), Err(PropertyError::UnknownName));
+    assert_eq!(resolve("RGI_Emoji", None), Err(PropertyError::UnknownName));
+
+    let emoji = resolve_property("RGI_Emoji", None, MatchingMode::Ecma262, true).unwrap();
+    assert!(emoji.may_contain_strings());
+    assert!(get_property_set(emoji).unwrap().has_strings());
+    assert_eq!(
+        get_property_unicode_set(emoji).err(),
+        Some(PropertyError::UnknownName)
+    );
+
+    let invalid = PropertyId { kind: 99, index: 0 };
+    assert_eq!(
+        get_property_set(invalid).err(),
+        Some(PropertyError::UnknownName)
+    );
+    for &kind in [SCRIPT, SCRIPT_EXTENSIONS].iter() {
+        let invalid = PropertyId {
+            kind,
+            index: u16::MAX,
+        };
+        assert!(invalid.property().is_none());
+        assert_eq!(
+            get_property_set(invalid).err(),
+            Some(PropertyError::UnknownName)
+        );
+    }
+
+    // The IDs are the ICU4X values, not positions in the tables.
+    let gc = resolve("gc", Some("Lu")).unwrap();
+    assert_eq!(gc.index, GeneralCategory::UppercaseLetter as u16);
+    let sc = resolve("sc", Some("Greek")).unwrap();
+    assert_eq!(
+        sc.index,
+        crate::tables::get_script("Greek", MatchingMode::Ecma262)
+            .unwrap()
+            .0
+    );
+}
@@ -0,0 +1,123 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+use crate::{blob_provider, script_of, ICU4XUniset, PropertyError};
+use icu_uniset::enum_props::Script;
+use icu_uniset::props::get_script_val_set;
+use icu_uniset::UnicodeSetBuilder;
+use std::cmp::Ordering;
+use std::ops::Deref;
+
+// Generated by build.rs from data/scx.toml. The ranges are sorted and
+// do not overlap.
+include!(concat!(env!("OUT_DIR"), "/scx.rs"));
+
+/// Returns the set of code points whose Script_Extensions value
+/// contains `script`.
+///
+/// Code points that do not appear in `SCRIPT_EXTENSIONS` have a
+/// Script_Extensions value of their Script value, so we start from the
+/// Script set and patch in the explicit Script_Extensions ranges.
+pub fn get_script_extensions_val_set(script: Script) -> Result<ICU4XUniset, PropertyError> {
+    let provider = blob_provider::get_static_provider();
+    let script_set =
+        get_script_val_set(provider, script).map_err(|_| PropertyError::DataUnavailable)?;
+
+    let mut builder = UnicodeSetBuilder::new();
+    builder.add_set(ICU4XUniset(script_set).get());
+    for (start, end, scripts) in SCRIPT_EXTENSIONS {
+        if scripts.contains(&script) {
+            builder.add_range(&(*start..=*end));
+        } else {
+            builder.remove_range(&(*start..=*end));
+        }
+    }
+    Ok(builder.build().into())
+}
+
+/// The Script_Extensions value of a code point: a list of scripts, which
+/// derefs to `[Script]`.
+#[derive(Clone, Copy, Debug)]
+pub struct ScriptExtensions {
+    explicit: &'static [Script],
+    script: [Script; 1],
+}
+
+impl Deref for ScriptExtensions {
+    type Target = [Script];
+
+    fn deref(&self) -> &[Script] {
+        if self.explicit.is_empty() {
+            &self.script
+        } else {
+            self.explicit
+        }
+    }
+}
+
+/// Returns the Script_Extensions value of a code point. This uses the
+/// same data as [`get_script_extensions_val_set`], so `code_point` is in
+/// the set of each script returned.
+pub fn script_extensions_of(code_point: u32) -> ScriptExtensions {
+    let explicit = SCRIPT_EXTENSIONS
+        .binary_search_by(|&(start, end, _)| {
+            if (end as u32) < code_point {
+                Ordering::Less
+            } else if (start as u32) > code_point {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .map_or(&[][..], |i| SCRIPT_EXTENSIONS[i].2);
+    ScriptExtensions {
+        explicit,
+        script: [script_of(code_point)],
+    }
+}
+
+#[test]
+fn test_script_extensions_of() {
+    use crate::tables::get_script;
+    use crate::MatchingMode;
+
+    let script = |name| get_script(name, MatchingMode::Ecma262).unwrap();
+    assert_eq!(&*script_extensions_of('A' as u32), &[script("Latn")]);
+
+    // U+3001 IDEOGRAPHIC COMMA
+    let comma = script_extensions_of(0x3001);
+    assert!(comma.contains(&script("Hira")));
+    assert!(comma.contains(&script("Hani")));
+    assert!(!comma.contains(&script("Zyyy")));
+
+    // U+10FB GEORGIAN PARAGRAPH SEPARATOR
+    assert_eq!(
+        &*script_extensions_of(0x10FB),
+        &[script("Geor"), script("Latn")]
+    );
+
+    // In Unicode 14.0, U+00B7 MIDDLE DOT and U+0589 ARMENIAN FULL STOP have
+    // no Script_Extensions beyond their Script.
+    assert_eq!(&*script_extensions_of(0xB7), &[script("Zyyy")]);
+    assert_eq!(&*script_extensions_of(0x589), &[script("Armn")]);
+
+    // Every code point is in the scx set of exactly the scripts returned.
+    let mut set_total = 0;
+    for &(_, _, script) in crate::tables::SCRIPTS {
+        let set = get_script_extensions_val_set(script).unwrap();
+        for i in 0..set.get().get_range_count() {
+            for code_point in set.get().get_nth_range(i).unwrap() {
+                assert!(
+                    script_extensions_of(code_point).contains(&script),
+                    "U+{:04X}",
+                    code_point
+                );
+                set_total += 1;
+            }
+        }
+    }
+    let total: usize = (0..=0x10FFFF)
+        .map(|code_point| script_extensions_of(code_point).len())
+        .sum();
+    assert_eq!(set_total, total);
+}
@@ -0,0 +1,284 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! Matching of a set of code points and strings in the order of the
+//! [CharacterClass](https://tc39.es/ecma262/multipage/text-processing.html#sec-compileatom)
+//! of a RegExp with the `v` flag: the strings longest first, then the code
+//! points, then the empty string.
+
+use crate::{canonicalize_unicode, ICU4XStringSet, ICU4XUniset};
+use std::collections::VecDeque;
+
+/// A node of the prefix trie of a [`StringSetMatcher`], in UTF-16 code
+/// units. The root is the first node, and the children of a node are
+/// stored together, sorted by their `unit`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StringTrieNode {
+    /// The code unit that leads from the parent to this node, or 0 for the
+    /// root.
+    pub unit: u16,
+    /// Whether the code units from the root to this node are a string of
+    /// the set.
+    pub is_end: bool,
+    /// The index of the first child.
+    pub first_child: u32,
+    /// The number of children.
+    pub child_count: u32,
+}
+
+/// A set of code points and strings, prepared for matching.
+///
+/// A bytecode compiler can emit the [`alternatives`](Self::alternatives)
+/// in order, followed by the [`code_points`](Self::code_points) and the
+/// empty string if [`matches_empty`](Self::matches_empty), or walk the
+/// [`trie`](Self::trie). The matcher can also be used directly with
+/// [`match_lengths`](Self::match_lengths) and
+/// [`longest_match`](Self::longest_match).
+pub struct StringSetMatcher {
+    set: ICU4XStringSet,
+    alternatives: Vec<Vec<u16>>,
+    trie: Vec<StringTrieNode>,
+    ignore_case: bool,
+}
+
+/// Builds the trie of `strings`, which are sorted, breadth first so that
+/// the children of each node are contiguous.
+fn build_trie(strings: &[Vec<u16>]) -> Vec<StringTrieNode> {
+    let node = |unit| StringTrieNode {
+        unit,
+        is_end: false,
+        first_child: 0,
+        child_count: 0,
+    };
+    let mut nodes = vec![node(0)];
+    let mut queue = VecDeque::new();
+    queue.push_back((0, 0..strings.len(), 0));
+    while let Some((parent, range, depth)) = queue.pop_front() {
+        // The string that ends here, if any, sorts before the longer ones.
+        let mut start = range.start;
+        if start < range.end && strings[start].len() == depth {
+            nodes[parent].is_end = true;
+            start += 1;
+        }
+        nodes[parent].first_child = nodes.len() as u32;
+        while start < range.end {
+            let unit = strings[start][depth];
+            let end = strings[start..range.end]
+                .iter()
+                .position(|string| string[depth] != unit)
+                .map_or(range.end, |length| start + length);
+            queue.push_back((nodes.len(), start..end, depth + 1));
+            nodes.push(node(unit));
+            nodes[parent].child_count += 1;
+            start = end;
+        }
+    }
+    nodes
+}
+
+/// The code point at `pos` in `input` and its length in code units. Lone
+/// surrogates are code points of their own.
+fn code_point_at(input: &[u16], pos: usize) -> Option<(u32, usize)> {
+    let c = std::char::decode_utf16(input.get(pos..)?.iter().copied()).next()?;
+    Some(match c {
+        Ok(c) => (c as u32, c.len_utf16()),
+        Err(e) => (e.unpaired_surrogate() as u32, 1),
+    })
+}
+
+impl StringSetMatcher {
+    /// Prepares `set` for matching. With `ignore_case`, the strings of the
+    /// set must be simple case folded, as they are with the `i` flag, and
+    /// the input is canonicalized before it is compared with them. The
+    /// code points match either as they are or canonicalized.
+    pub fn new(set: ICU4XStringSet, ignore_case: bool) -> Self {
+        let strings: Vec<Vec<u16>> = set
+            .strings()
+            .iter()
+            .filter(|string| !string.is_empty())
+            .cloned()
+            .collect();
+        let trie = build_trie(&strings);
+        let mut alternatives = strings;
+        alternatives.sort_by_cached_key(|string| {
+            std::cmp::Reverse(std::char::decode_utf16(string.iter().copied()).count())
+        });
+        StringSetMatcher {
+            set,
+            alternatives,
+            trie,
+            ignore_case,
+        }
+    }
+
+    /// The strings of more than one code point, in the order in which
+    /// they are tried: longest first, in code points.
+    pub fn alternatives(&self) -> &[Vec<u16>] {
+        &self.alternatives
+    }
+
+    /// The code points, which are tried after the strings.
+    pub fn code_points(&self) -> &ICU4XUniset {
+        self.set.code_points()
+    }
+
+    /// Whether the set contains the empty string, which is tried last.
+    pub fn matches_empty(&self) -> bool {
+        self.set.strings().first().is_some_and(Vec::is_empty)
+    }
+
+    /// The prefix trie of the [`alternatives`](Self::alternatives).
+    pub fn trie(&self) -> &[StringTrieNode] {
+        &self.trie
+    }
+
+    fn child(&self, node: &StringTrieNode, unit: u16) -> Option<&StringTrieNode> {
+        let start = node.first_child as usize;
+        let children = &self.trie[start..start + node.child_count as usize];
+        children
+            .binary_search_by_key(&unit, |child| child.unit)
+            .ok()
+            .map(|i| &children[i])
+    }
+
+    /// The lengths in code units of the strings that match `input` at
+    /// `pos`, longest first.
+    fn string_match_lengths(&self, input: &[u16], mut pos: usize) -> Vec<usize> {
+        let start = pos;
+        let mut lengths = vec![];
+        let mut node = &self.trie[0];
+        'input: while let Some((mut c, length)) = code_point_at(input, pos) {
+            if self.ignore_case {
+                c = canonicalize_unicode(c);
+            }
+            let mut units = [0; 2];
+            let units = match std::char::from_u32(c) {
+                Some(c) => &*c.encode_utf16(&mut units),
+                None => {
+                    units[0] = c as u16;
+                    &units[..1]
+                }
+            };
+            for &unit in units {
+                node = match self.child(node, unit) {
+                    Some(child) => child,
+                    None => break 'input,
+                };
+            }
+            pos += length;
+            if node.is_end {
+                lengths.push(pos - start);
+            }
+        }
+        lengths.reverse();
+        lengths
+    }
+
+    /// The length in code units of the code point at `pos`, if it is in
+    /// the set.
+    fn code_point_match_length(&self, input: &[u16], pos: usize) -> Option<usize> {
+        let (c, length) = code_point_at(input, pos)?;
+        let code_points = self.code_points().get();
+        let contains = |c| match std::char::from_u32(c) {
+            Some(c) => code_points.contains(c),
+            // A surrogate.
+            None => (0..code_points.get_range_count())
+                .filter_map(|i| code_points.get_nth_range(i))
+                .any(|range| range.contains(&c)),
+        };
+        if contains(c) || (self.ignore_case && contains(canonicalize_unicode(c))) {
+            Some(length)
+        } else {
+            None
+        }
+    }
+
+    /// The lengths in code units of every match at `pos` in `input`, in
+    /// the order in which a backtracking matcher tries them.
+    pub fn match_lengths(&self, input: &[u16], pos: usize) -> Vec<usize> {
+        let mut lengths = self.string_match_lengths(input, pos);
+        lengths.extend(self.code_point_match_length(input, pos));
+        if self.matches_empty() && pos <= input.len() {
+            lengths.push(0);
+        }
+        lengths
+    }
+
+    /// The length in code units of the first match at `pos` in `input`,
+    /// which is the longest.
+    pub fn longest_match(&self, input: &[u16], pos: usize) -> Option<usize> {
+        self.match_lengths(input, pos).first().copied()
+    }
+}
+
+#[test]
+fn test_string_set_matcher() {
+    use crate::{compile_class, RegExpFlags};
+
+    let utf16 = |s: &str| s.encode_utf16().collect::<Vec<u16>>();
+    let v = RegExpFlags {
+        unicode_sets: true,
+        ..RegExpFlags::default()
+    };
+    let matcher = |pattern: &str, flags| {
+        let (set, _) = compile_class(&utf16(pattern), 1, flags).unwrap();
+        StringSetMatcher::new(set, flags.ignore_case)
+    };
+
+    let m = matcher("[\\q{abc|ab|xy|}a-c]", v);
+    assert_eq!(m.alternatives(), [utf16("abc"), utf16("ab"), utf16("xy")]);
+    assert!(m.matches_empty());
+    assert_eq!(m.code_points().get().size(), 3);
+    assert_eq!(m.match_lengths(&utf16("abcd"), 0), [3, 2, 1, 0]);
+    assert_eq!(m.match_lengths(&utf16("abcd"), 1), [1, 0]);
+    assert_eq!(m.match_lengths(&utf16("xyz"), 0), [2, 0]);
+    assert_eq!(m.match_lengths(&utf16("ab"), 2), [0]);
+    assert_eq!(m.longest_match(&utf16("zab"), 1), Some(2));
+
+    // The trie is breadth first: the root, `a` and `x`, `b` and `y`, `c`.
+    let units: Vec<u16> = m.trie().iter().map(|node| node.unit).collect();
+    assert_eq!(units, utf16("\0axbyc"));
+    let ends: Vec<bool> = m.trie().iter().map(|node| node.is_end).collect();
+    assert_eq!(ends, [false, false, false, true, true, true]);
+    assert_eq!((m.trie()[1].first_child, m.trie()[1].child_count), (3, 1));
+
+    // [\p{RGI_Emoji}--\q{🇫🇷}]
+    let m = matcher("[\\p{RGI_Emoji}--\\q{\u{1F1EB}\u{1F1F7}}]", v);
+    assert!(!m.matches_empty());
+    let lengths = |s: &str| m.match_lengths(&utf16(s), 0);
+    assert_eq!(lengths("\u{1F1EB}\u{1F1F7}"), []);
+    assert_eq!(lengths("\u{1F1E9}\u{1F1EA}"), [4]);
+    assert_eq!(lengths("\u{1F600}!"), [2]);
+    // 👩‍❤️‍👨 and the 👩 that starts it.
+    assert_eq!(
+        lengths("\u{1F469}\u{200D}\u{2764}\u{FE0F}\u{200D}\u{1F468}"),
+        [8, 2]
+    );
+    let lengths: Vec<usize> = m
+        .alternatives()
+        .iter()
+        .map(|string| String::from_utf16(string).unwrap().chars().count())
+        .collect();
+    assert!(lengths.windows(2).all(|pair| pair[0] >= pair[1]));
+
+    // With `i`, the strings are folded and the input is canonicalized.
+    let iv = RegExpFlags {
+        ignore_case: true,
+        ..v
+    };
+    let m = matcher("[\\q{KA}]", iv);
+    assert_eq!(m.alternatives(), [utf16("ka")]);
+    assert_eq!(m.longest_match(&utf16("\u{212A}A"), 0), Some(2));
+    assert_eq!(m.longest_match(&utf16("kb"), 0), None);
+
+    // Lone surrogates are matched as code points.
+    let m = StringSetMatcher::new(
+        crate::ClassSetExpression::Character(0xD800)
+            .evaluate(false)
+            .unwrap(),
+        false,
+    );
+    assert_eq!(m.longest_match(&[0xD800, 'a' as u16], 0), Some(1));
+    assert_eq!(m.longest_match(&[0xD800, 0xDC00], 0), None);
+}
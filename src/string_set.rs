@@ -0,0 +1,54 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+use crate::ICU4XUniset;
+use icu_uniset::UnicodeSet;
+
+/// A set of code points and strings, like the sets matched by the
+/// properties of strings of RegExps with the `v` flag.
+///
+/// Strings are stored as UTF-16, in sorted order, without duplicates.
+pub struct ICU4XStringSet {
+    code_points: ICU4XUniset,
+    strings: Vec<Vec<u16>>,
+}
+
+impl ICU4XStringSet {
+    pub(crate) fn new(code_points: ICU4XUniset, mut strings: Vec<Vec<u16>>) -> Self {
+        strings.sort();
+        strings.dedup();
+        ICU4XStringSet {
+            code_points,
+            strings,
+        }
+    }
+
+    /// The code points in the set.
+    pub fn code_points(&self) -> &ICU4XUniset {
+        &self.code_points
+    }
+
+    /// The strings in the set, each of which is a sequence of more than
+    /// one code point, or the empty string.
+    pub fn strings(&self) -> &[Vec<u16>] {
+        &self.strings
+    }
+
+    /// Whether the set contains any strings. A property escape for such
+    /// a set cannot be negated with `\P`.
+    pub fn has_strings(&self) -> bool {
+        !self.strings.is_empty()
+    }
+}
+
+impl From<ICU4XUniset> for ICU4XStringSet {
+    fn from(code_points: ICU4XUniset) -> Self {
+        ICU4XStringSet::new(code_points, vec![])
+    }
+}
+
+impl From<UnicodeSet<'static>> for ICU4XStringSet {
+    fn from(uniset: UnicodeSet<'static>) -> Self {
+        ICU4XUniset::from(uniset).into()
+    }
+}
@@ -0,0 +1,113 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+use crate::catalog::{properties, PropertyInfo, PropertyKind};
+use crate::tables::loose_key;
+
+/// Returns the closest valid spellings of a rejected property name or
+/// value, for "did you mean" error messages.
+///
+/// If `prop_name` is an enumerated property and `prop_value` is given,
+/// the suggestions are values of that property. Otherwise they are
+/// property names that can be used in the same way as `prop_name`.
+///
+/// Names are compared as in [UAX44-LM3](https://unicode.org/reports/tr44/#UAX44-LM3),
+/// and all of the names at the smallest edit distance are returned. The
+/// result is empty if no name is close enough.
+pub fn suggest_property_names(prop_name: &str, prop_value: Option<&str>) -> Vec<&'static str> {
+    let (name, candidates) = get_candidates(prop_name, prop_value);
+
+    let key = loose_key(name);
+    let max_distance = (key.chars().count() / 3).max(1);
+    let mut best = vec![];
+    let mut best_distance = max_distance + 1;
+    for candidate in candidates {
+        let distance = edit_distance(&key, &loose_key(candidate));
+        if distance < best_distance {
+            best.clear();
+            best_distance = distance;
+        }
+        if distance == best_distance && !best.contains(&candidate) {
+            best.push(candidate);
+        }
+    }
+    best
+}
+
+/// Returns the rejected name and the names it could have been meant as.
+fn get_candidates<'a>(
+    prop_name: &'a str,
+    prop_value: Option<&'a str>,
+) -> (&'a str, Vec<&'static str>) {
+    let names_of = |kind: PropertyKind| -> Vec<&'static str> {
+        properties()
+            .iter()
+            .filter(|prop| prop.kind() == kind)
+            .flat_map(|prop| prop.names().iter().copied())
+            .collect()
+    };
+    let values_of = |prop: &PropertyInfo| -> Vec<&'static str> {
+        prop.values()
+            .iter()
+            .flat_map(|value| value.names().iter().copied())
+            .collect()
+    };
+    let find_enumerated = |name: &str| {
+        properties()
+            .iter()
+            .find(|prop| prop.kind() == PropertyKind::Enumerated && prop.names().contains(&name))
+    };
+
+    match prop_value {
+        Some(value) => match find_enumerated(prop_name) {
+            Some(prop) => (value, values_of(prop)),
+            None => (prop_name, names_of(PropertyKind::Enumerated)),
+        },
+        // A lone name is either a binary property or a General_Category
+        // value.
+        None => {
+            let mut names = names_of(PropertyKind::Binary);
+            if let Some(gc) = find_enumerated("General_Category") {
+                names.extend(values_of(gc));
+            }
+            (prop_name, names)
+        }
+    }
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = diagonal + if a_char == b_char { 0 } else { 1 };
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[test]
+fn test_suggestions() {
+    assert_eq!(
+        suggest_property_names("Sentence_terminal", None),
+        ["Sentence_Terminal"]
+    );
+    assert_eq!(suggest_property_names("Alphabetical", None), ["Alphabetic"]);
+    assert_eq!(
+        suggest_property_names("uppercase_letter", None),
+        ["Uppercase_Letter"]
+    );
+    assert_eq!(suggest_property_names("sc", Some("greek")), ["Greek"]);
+    assert_eq!(suggest_property_names("sc", Some("Cyrilic")), ["Cyrillic"]);
+    assert_eq!(suggest_property_names("Scirpt", Some("Greek")), ["Script"]);
+    assert!(suggest_property_names("Xyzzy", None).is_empty());
+    assert!(suggest_property_names("sc", Some("Xyzzy")).is_empty());
+
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("", "abc"), 3);
+}
@@ -0,0 +1,302 @@
+// This file is licensed under the same terms as ICU4X.
+// For details, please see the LICENSE file.
+
+//! The property name and value alias tables of the ECMAScript specification.
+
+use icu_uniset::enum_props::{GeneralCategory, Script};
+
+// Tables 69-72 are generated by build.rs from data/PropertyAliases.txt,
+// data/PropertyValueAliases.txt and the .toml files in data/:
+//
+// ENUMERATED_PROPERTIES: Table 69
+// BINARY_PROPERTIES: Table 70, except for the special cases below. Each
+//   row holds the key of the property's data in the static blob, its
+//   names, and the property itself.
+// GENERAL_CATEGORIES: Table 71, with the same layout.
+// SCRIPTS: Table 72, with the same layout.
+include!(concat!(env!("OUT_DIR"), "/tables.rs"));
+
+// Table 69: Non-binary Unicode property aliases and their canonical property names
+// https://tc39.es/ecma262/multipage/text-processing.html#table-nonbinary-unicode-properties
+#[derive(Clone, Copy)]
+pub(crate) enum EnumeratedProperty {
+    GeneralCategory,
+    Script,
+    ScriptExtensions,
+}
+
+// Table 70: Binary Unicode property aliases and their canonical property names
+// https://tc39.es/ecma262/multipage/text-processing.html#table-binary-unicode-properties
+//
+// The discriminants are stored in a `PropertyId`, so they must not change:
+// new properties take new values.
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinaryProperty {
+    Alphabetic = 0,
+    AsciiHexDigit = 1,
+    BidiControl = 2,
+    BidiMirrored = 3,
+    CaseIgnorable = 4,
+    Cased = 5,
+    ChangesWhenCasefolded = 6,
+    ChangesWhenCasemapped = 7,
+    ChangesWhenLowercased = 8,
+    ChangesWhenNfkcCasefolded = 9,
+    ChangesWhenTitlecased = 10,
+    ChangesWhenUppercased = 11,
+    Dash = 12,
+    DefaultIgnorableCodePoint = 13,
+    Deprecated = 14,
+    Diacritic = 15,
+    Emoji = 16,
+    EmojiComponent = 17,
+    EmojiModifierBase = 18,
+    EmojiModifier = 19,
+    EmojiPresentation = 20,
+    ExtendedPictographic = 21,
+    Extender = 22,
+    GraphemeBase = 23,
+    GraphemeExtend = 24,
+    HexDigit = 25,
+    IdsBinaryOperator = 26,
+    IdsTrinaryOperator = 27,
+    IdContinue = 28,
+    IdStart = 29,
+    Ideographic = 30,
+    JoinControl = 31,
+    LogicalOrderException = 32,
+    Lowercase = 33,
+    Math = 34,
+    NoncharacterCodePoint = 35,
+    PatternSyntax = 36,
+    PatternWhiteSpace = 37,
+    QuotationMark = 38,
+    Radical = 39,
+    RegionalIndicator = 40,
+    SentenceTerminal = 41,
+    SoftDotted = 42,
+    TerminalPunctuation = 43,
+    UnifiedIdeograph = 44,
+    Uppercase = 45,
+    VariationSelector = 46,
+    WhiteSpace = 47,
+    XidContinue = 48,
+    XidStart = 49,
+
+    // Special cases: See https://unicode.org/reports/tr18/#General_Category_Property
+    Ascii = 50,
+    Any = 51,
+    Assigned = 52,
+}
+
+#[rustfmt::skip]
+pub(crate) static SPECIAL_BINARY_PROPERTIES: &[(&[&str], BinaryProperty)] = &[
+    (&["ASCII"], BinaryProperty::Ascii),
+    (&["Any"], BinaryProperty::Any),
+    (&["Assigned"], BinaryProperty::Assigned),
+];
+
+// Binary Unicode properties of strings, supported only with the `v` flag
+// https://tc39.es/ecma262/multipage/text-processing.html#table-binary-unicode-properties-of-strings
+// As with `BinaryProperty`, the discriminants must not change.
+#[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StringProperty {
+    BasicEmoji = 0,
+    EmojiKeycapSequence = 1,
+    RgiEmoji = 2,
+    RgiEmojiFlagSequence = 3,
+    RgiEmojiModifierSequence = 4,
+    RgiEmojiTagSequence = 5,
+    RgiEmojiZwjSequence = 6,
+}
+
+#[rustfmt::skip]
+pub(crate) static STRING_PROPERTIES: &[(&[&str], StringProperty)] = &[
+    (&["Basic_Emoji"], StringProperty::BasicEmoji),
+    (&["Emoji_Keycap_Sequence"], StringProperty::EmojiKeycapSequence),
+    (&["RGI_Emoji"], StringProperty::RgiEmoji),
+    (&["RGI_Emoji_Flag_Sequence"], StringProperty::RgiEmojiFlagSequence),
+    (&["RGI_Emoji_Modifier_Sequence"], StringProperty::RgiEmojiModifierSequence),
+    (&["RGI_Emoji_Tag_Sequence"], StringProperty::RgiEmojiTagSequence),
+    (&["RGI_Emoji_ZWJ_Sequence"], StringProperty::RgiEmojiZwjSequence),
+];
+
+/// How property names and values are matched against the names in the
+/// alias tables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchingMode {
+    /// Names must match exactly, as required by ECMA-262.
+    Ecma262,
+    /// Names are matched following
+    /// [UAX44-LM3](https://unicode.org/reports/tr44/#UAX44-LM3): case,
+    /// whitespace, hyphens and underscores are ignored, as is an
+    /// initial "is" prefix.
+    Uax44Loose,
+}
+
+/// The characters of a name that UAX44-LM3 compares, lowercased and
+/// without whitespace, hyphens, underscores or an initial "is".
+fn loose_chars(name: &str) -> impl Iterator<Item = char> + Clone + '_ {
+    let chars = name
+        .chars()
+        .filter(|&c| !c.is_whitespace() && c != '_' && c != '-')
+        .flat_map(char::to_lowercase);
+    let mut rest = chars.clone();
+    if rest.next() == Some('i') && rest.next() == Some('s') {
+        rest
+    } else {
+        chars
+    }
+}
+
+pub(crate) fn loose_key(name: &str) -> String {
+    loose_chars(name).collect()
+}
+
+fn matches(names: &[&str], name: &str, mode: MatchingMode) -> bool {
+    match mode {
+        MatchingMode::Ecma262 => names.contains(&name),
+        MatchingMode::Uax44Loose => names
+            .iter()
+            .any(|alias| loose_chars(alias).eq(loose_chars(name))),
+    }
+}
+
+pub(crate) fn get_enumerated_property(
+    prop_name: &str,
+    mode: MatchingMode,
+) -> Option<EnumeratedProperty> {
+    ENUMERATED_PROPERTIES
+        .iter()
+        .find(|(names, _)| matches(names, prop_name, mode))
+        .map(|(_, prop)| *prop)
+}
+
+pub(crate) fn get_binary_property(prop_name: &str, mode: MatchingMode) -> Option<BinaryProperty> {
+    BINARY_PROPERTIES
+        .iter()
+        .map(|(_, names, prop)| (names, prop))
+        .chain(
+            SPECIAL_BINARY_PROPERTIES
+                .iter()
+                .map(|(names, prop)| (names, prop)),
+        )
+        .find(|(names, _)| matches(names, prop_name, mode))
+        .map(|(_, prop)| *prop)
+}
+
+pub(crate) fn get_general_category(gc_name: &str, mode: MatchingMode) -> Option<GeneralCategory> {
+    GENERAL_CATEGORIES
+        .iter()
+        .find(|(_, names, _)| matches(names, gc_name, mode))
+        .map(|(_, _, gc)| *gc)
+}
+
+pub(crate) fn get_script(script_name: &str, mode: MatchingMode) -> Option<Script> {
+    SCRIPTS
+        .iter()
+        .find(|(_, names, _)| matches(names, script_name, mode))
+        .map(|(_, _, script)| *script)
+}
+
+pub(crate) fn get_string_property(prop_name: &str, mode: MatchingMode) -> Option<StringProperty> {
+    STRING_PROPERTIES
+        .iter()
+        .find(|(names, _)| matches(names, prop_name, mode))
+        .map(|(_, prop)| *prop)
+}
+
+#[test]
+fn test_ecma262_aliases() {
+    let fixture = include_str!("../tests/fixtures/ecma262_aliases.txt");
+
+    let mut tables: Vec<(&str, Vec<&str>)> = vec![];
+    for line in fixture.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            tables.push((line.trim_matches(&['[', ']'][..]), vec![]));
+        } else {
+            tables.last_mut().unwrap().1.extend(line.split_whitespace());
+        }
+    }
+
+    let all_names: Vec<&str> = tables.iter().flat_map(|(_, names)| names.clone()).collect();
+    for (table, names) in &tables {
+        let lookup = |name: &str| match *table {
+            "Table 69" => get_enumerated_property(name, MatchingMode::Ecma262).is_some(),
+            "Table 70" => get_binary_property(name, MatchingMode::Ecma262).is_some(),
+            "Table 71" => get_general_category(name, MatchingMode::Ecma262).is_some(),
+            "Table 72" => get_script(name, MatchingMode::Ecma262).is_some(),
+            _ => panic!("Unexpected table: {}", table),
+        };
+
+        for &name in names {
+            assert!(lookup(name), "{} should be in {}", name, table);
+
+            let near_misses = [
+                name.to_lowercase(),
+                name.to_uppercase(),
+                name.replace('_', ""),
+                format!("Is{}", name),
+            ];
+            for near_miss in near_misses.iter() {
+                if !all_names.contains(&near_miss.as_str()) {
+                    assert!(
+                        !lookup(near_miss.as_str()),
+                        "{} should not be in {}",
+                        near_miss,
+                        table
+                    );
+                }
+            }
+        }
+    }
+
+    // Every alias in the tables should be in the fixture.
+    let table_names = ENUMERATED_PROPERTIES
+        .iter()
+        .flat_map(|(names, _)| names.iter())
+        .chain(
+            BINARY_PROPERTIES
+                .iter()
+                .flat_map(|(_, names, _)| names.iter()),
+        )
+        .chain(
+            SPECIAL_BINARY_PROPERTIES
+                .iter()
+                .flat_map(|(names, _)| names.iter()),
+        )
+        .chain(
+            GENERAL_CATEGORIES
+                .iter()
+                .flat_map(|(_, names, _)| names.iter()),
+        )
+        .chain(SCRIPTS.iter().flat_map(|(_, names, _)| names.iter()));
+    for name in table_names {
+        assert!(all_names.contains(name), "{} is not in ECMA-262", name);
+    }
+}
+
+#[test]
+fn test_loose_matching() {
+    let loose = MatchingMode::Uax44Loose;
+    assert!(get_script("greek", loose).is_some());
+    assert!(get_script(" Old-Italic ", loose).is_some());
+    assert!(get_enumerated_property("Script ", loose).is_some());
+    assert!(get_binary_property("IsAlphabetic", loose).is_some());
+    assert!(get_binary_property("white space", loose).is_some());
+    assert!(get_general_category("isLu", loose).is_some());
+    assert!(get_general_category("uppercaseletter", loose).is_some());
+    assert!(get_script("Is-Latn", loose).is_some());
+    assert!(get_script("Lat", loose).is_none());
+    assert!(get_script("Latin_", loose).is_some());
+    assert_eq!(loose_key(" Is_Old-Italic"), "olditalic");
+
+    assert!(get_script("greek", MatchingMode::Ecma262).is_none());
+    assert!(get_binary_property("IsAlphabetic", MatchingMode::Ecma262).is_none());
+}